@@ -6,7 +6,15 @@ use leptos::*;
 pub fn PageColorPicker() -> impl IntoView {
     let (hsv, set_hsv) = create_signal(HSV::new());
 
+    let (hsva, set_hsva) = create_signal(Alpha::new(HSV::new(), 0.5));
+
+    let (hsv_swatches, set_hsv_swatches) = create_signal(HSV::new());
+    let recent = RecentColors::new("book-recent-colors");
+
+    let (gradient, set_gradient) = create_signal(Gradient::default());
+
     let (hsv_test, set_hsv_test) = create_signal(HSV::new());
+    let (alpha_test, set_alpha_test) = create_signal(0.5);
     let hsv_test_rgb_preview = Signal::derive(move || hsv_test.get().into_rgb8());
 
     view! {
@@ -25,6 +33,98 @@ pub fn PageColorPicker() -> impl IntoView {
 
         <ColorPicker hsv=hsv set_hsv=set_hsv/>
 
+        <H2>"Transparency"</H2>
+
+        <P>
+            "Provide an "<Code inline=true>"Alpha<HSV>"</Code>" using the "<Code inline=true>"hsva"</Code>" and "<Code inline=true>"set_hsva"</Code>" props "
+            "instead of "<Code inline=true>"hsv"</Code>" and "<Code inline=true>"set_hsv"</Code>" to also pick the opacity of the color."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                let (hsva, set_hsva) = create_signal(Alpha::new(HSV::new(), 0.5));
+                view! {
+                    <ColorPicker hsva=hsva set_hsva=set_hsva/>
+                }
+            "#)}
+        </Code>
+
+        <ColorPicker hsva=hsva set_hsva=set_hsva/>
+
+        <H2>"Swatches"</H2>
+
+        <P>
+            "Preset colors can be offered using the "<Code inline=true>"swatches"</Code>" prop. "
+            "A swatch is either a fixed color or a color variable of the active theme, which is looked up when clicked. "
+            <Code inline=true>"Swatch::theme()"</Code>" lists the brand and semantic colors of the bundled themes."
+        </P>
+
+        <P>
+            "Colors picked by the user are remembered when passing "<Code inline=true>"RecentColors"</Code>", which are persisted in local storage under the given key. "
+            "Share a single "<Code inline=true>"RecentColors"</Code>" value between all components showing the same recent colors."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                let (hsv, set_hsv) = create_signal(HSV::new());
+                let recent = RecentColors::new("recent-colors");
+                let mut swatches = Swatch::theme();
+                swatches.push(Swatch::from(RGB8::from((0x0b, 0x53, 0x94))));
+                view! {
+                    <ColorPicker hsv=hsv set_hsv=set_hsv swatches=swatches recent=recent/>
+                }
+            "#)}
+        </Code>
+
+        <ColorPicker
+            hsv=hsv_swatches
+            set_hsv=set_hsv_swatches
+            swatches={
+                let mut swatches = Swatch::theme();
+                swatches.push(Swatch::from(RGB8::from((0x0b, 0x53, 0x94))));
+                swatches
+            }
+            recent=recent
+        />
+
+        <P>"The "<Code inline=true>"<ColorSwatches>"</Code>" component can also be used on its own."</P>
+
+        <Code>
+            {indoc!(r#"
+                view! {
+                    <ColorSwatches
+                        swatches=Swatch::theme()
+                        set_color=move |color: RGBA8| set_hsv.set(HSV::from(color.rgb8()))
+                        recent=recent
+                    />
+                }
+            "#)}
+        </Code>
+
+        <H2>"Gradients"</H2>
+
+        <P>
+            "The "<Code inline=true>"<GradientPicker>"</Code>" component edits a linear or radial "<Code inline=true>"Gradient"</Code>". "
+            "Drag the color stops along the bar, press the bar to add a stop at that position and edit the color of the selected stop using the color picker below. "
+            "The selected stop is removed using the delete key. A gradient always keeps at least two stops."
+        </P>
+
+        <P>"A "<Code inline=true>"Gradient"</Code>" renders to the CSS value it describes, e.g. "<Code inline=true>"linear-gradient(90deg, #FF0000 0%, #0000FF 100%)"</Code>"."</P>
+
+        <Code>
+            {indoc!(r#"
+                let (gradient, set_gradient) = create_signal(Gradient::default());
+                view! {
+                    <GradientPicker gradient=gradient set_gradient=set_gradient/>
+                    <div style=move || format!("height: 3em; background: {}", gradient.get())/>
+                }
+            "#)}
+        </Code>
+
+        <GradientPicker gradient=gradient set_gradient=set_gradient/>
+
+        <P>"CSS: "<Code inline=true>{move || gradient.get().to_string()}</Code></P>
+
         <H2>"Parts"</H2>
 
         <P>"The "<Code inline=true>"<ColorPicker>"</Code>" build on top of a few other components build to help work with colors. You may use them directly and build your own color picker."</P>
@@ -52,6 +152,18 @@ pub fn PageColorPicker() -> impl IntoView {
 
         <ColorPreview rgb=hsv_test_rgb_preview style="width: 5em%; height: 5em;"/>
 
+        <P>"Given an "<Code inline=true>"alpha"</Code>" signal, transparent colors are rendered in front of a checkerboard pattern."</P>
+
+        <Code>
+            {indoc!(r#"
+                view! {
+                    <ColorPreview rgb=rgb alpha=alpha style="width: 5em%; height: 5em;"/>
+                }
+            "#)}
+        </Code>
+
+        <ColorPreview rgb=hsv_test_rgb_preview alpha=alpha_test style="width: 5em%; height: 5em;"/>
+
         <H3>ColorPalette</H3>
 
         <P>
@@ -104,6 +216,41 @@ pub fn PageColorPicker() -> impl IntoView {
             set_hue=move |hue| set_hsv_test.update(|hsv| hsv.hue = hue)
         />
 
+        <H3>AlphaSlider</H3>
+
+        <P>
+            "The "<Code inline=true>"<AlphaSlider>"</Code>" component picks an opacity between 0.0 and 1.0. "
+            "The slider background fades the given RGB color in front of a checkerboard pattern."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                let (alpha, set_alpha) = create_signal(0.5);
+                view! {
+                    <AlphaSlider alpha=alpha set_alpha=set_alpha rgb=rgb/>
+                }
+            "#)}
+        </Code>
+
+        <AlphaSlider alpha=alpha_test set_alpha=set_alpha_test rgb=hsv_test_rgb_preview/>
+
+        <H3>ColorInputs</H3>
+
+        <P>
+            "The "<Code inline=true>"<ColorInputs>"</Code>" component lets users type an exact color, either as a hex color or by its RGB, HSV or HSL components. "
+            "Invalid input is highlighted and ignored. Valid input is applied immediately, while editing one component never changes any of the others."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                view! {
+                    <ColorInputs hsv=hsv set_hsv=set_hsv format=ColorFormat::Rgb/>
+                }
+            "#)}
+        </Code>
+
+        <ColorInputs hsv=hsv_test set_hsv=set_hsv_test format=ColorFormat::Rgb/>
+
         <P>"If you look at the source of Leptonic's <ColorPicker>, you will see that there is not much more to it as what you saw here!"</P>
 
         <H2>"Styling"</H2>
@@ -123,6 +270,22 @@ pub fn PageColorPicker() -> impl IntoView {
                 --color-palette-knob-halo-background-color
                 --color-palette-knob-transition-speed
                 --color-palette-knob-box-shadow
+                --color-checkerboard-size
+                --color-checkerboard-light
+                --color-checkerboard-dark
+                --color-input-invalid-border-color
+                --color-swatch-size
+                --color-swatch-gap
+                --color-swatch-border-radius
+                --color-swatch-border
+                --color-swatch-focus-outline
+                --gradient-picker-preview-height
+                --gradient-picker-bar-height
+                --gradient-picker-border-radius
+                --gradient-picker-stop-size
+                --gradient-picker-stop-border
+                --gradient-picker-stop-box-shadow
+                --gradient-picker-stop-selected-outline
             "#)}
         </Code>
     }
@@ -12,7 +12,8 @@ pub fn PageDrawer() -> impl IntoView {
 
         <P>
             "The "<Code inline=true>"<Drawer>"</Code>" component is intended to be used as a side menu. It is animated to conditionally move in and out of visibility."
-            "The required "<Code inline=true>"side"</Code>" prop controls to which side the drawer should move when hiding."
+            "The required "<Code inline=true>"side"</Code>" prop controls to which side the drawer should move when hiding. "
+            "Use "<Code inline=true>"DrawerSide::Start"</Code>" or "<Code inline=true>"DrawerSide::End"</Code>" to let the side follow the "<Code inline=true>"direction"</Code>" given to "<Code inline=true>"<Root>"</Code>"."
         </P>
 
         <Toggle state=shown set_state=set_shown/>
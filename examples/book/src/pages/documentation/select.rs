@@ -31,6 +31,39 @@ impl std::fmt::Display for User {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Country {
+    name: &'static str,
+    continent: &'static str,
+}
+
+const COUNTRIES: [Country; 6] = [
+    Country {
+        name: "Chile",
+        continent: "South America",
+    },
+    Country {
+        name: "France",
+        continent: "Europe",
+    },
+    Country {
+        name: "Japan",
+        continent: "Asia",
+    },
+    Country {
+        name: "Kenya",
+        continent: "Africa",
+    },
+    Country {
+        name: "Peru",
+        continent: "South America",
+    },
+    Country {
+        name: "Spain",
+        continent: "Europe",
+    },
+];
+
 #[component]
 pub fn PageSelect() -> impl IntoView {
     let (selected, set_selected) = create_signal(Foo::A);
@@ -51,6 +84,17 @@ pub fn PageSelect() -> impl IntoView {
 
     let (selected_user, set_selected_user) = create_signal(selectable_users[0].clone());
 
+    let (selected_number, set_selected_number) = create_signal(Option::<u32>::None);
+
+    let (selected_countries, set_selected_countries) = create_signal(Vec::<Country>::new());
+
+    let (tags, set_tags) = create_signal(vec!["leptos".to_owned(), "rust".to_owned()]);
+
+    let (selected_country, set_selected_country) = create_signal(Option::<Country>::None);
+
+    let many_numbers = (0..20_000u32).collect::<Vec<_>>();
+    let (selected_many, set_selected_many) = create_signal(0u32);
+
     view! {
         <H1>"Selects"</H1>
 
@@ -241,6 +285,190 @@ pub fn PageSelect() -> impl IntoView {
             set_selected=move |v| set_selected_user.set(v)
         />
 
+        <H2>"Groups"</H2>
+
+        <P>
+            "Options can be shown under group headers by providing a "<Code inline=true>"group_provider"</Code>", returning the name of the group of an option. "
+            "Options of the same group are shown together, in the order of the first option of each group. "
+            "Group headers can not be selected and are skipped when navigating with the arrow keys. Groups without any option matching the search are hidden."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                view! {
+                    <Multiselect
+                        options=COUNTRIES.to_vec()
+                        search_text_provider=move |c: Country| c.name.to_owned()
+                        group_provider=move |c: Country| c.continent.to_owned()
+                        render_option=move |c: Country| c.name
+                        selected=selected_countries
+                        set_selected=set_selected_countries
+                    />
+                }
+            "#)}
+        </Code>
+
+        <Multiselect
+            options=COUNTRIES.to_vec()
+            search_text_provider=move |c: Country| c.name.to_owned()
+            group_provider=move |c: Country| c.continent.to_owned()
+            render_option=move |c: Country| c.name
+            selected=selected_countries
+            set_selected=set_selected_countries
+        />
+
+        <H2>"Creating options"</H2>
+
+        <P>
+            "A "<Code inline=true>"<Multiselect>"</Code>" with a "<Code inline=true>"create_option"</Code>" callback also accepts values which are not offered. "
+            "Pressing "<Code inline=true>"Enter"</Code>" while no option is preselected adds the search text, using an offered option if its search text is equal and constructing a new one otherwise. "
+            "Pasting comma- or newline-separated text adds all of its parts at once, making the select usable as a tags input."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                let (tags, set_tags) = create_signal(vec!["leptos".to_owned(), "rust".to_owned()]);
+
+                view! {
+                    <Multiselect
+                        options=vec!["css".to_owned(), "leptos".to_owned(), "rust".to_owned(), "wasm".to_owned()]
+                        search_text_provider=move |tag: String| tag
+                        render_option=move |tag: String| tag
+                        create_option=move |tag: String| tag.to_lowercase()
+                        selected=tags
+                        set_selected=set_tags
+                    />
+                }
+            "#)}
+        </Code>
+
+        <Multiselect
+            options=vec!["css".to_owned(), "leptos".to_owned(), "rust".to_owned(), "wasm".to_owned()]
+            search_text_provider=move |tag: String| tag
+            render_option=move |tag: String| tag
+            create_option=move |tag: String| tag.to_lowercase()
+            selected=tags
+            set_selected=set_tags
+        />
+
+        <H2>"Matching and highlighting"</H2>
+
+        <P>
+            "The search text is matched against the text returned by "<Code inline=true>"search_text_provider"</Code>" using a "<Code inline=true>"Matcher"</Code>", ignoring case. "
+            <Code inline=true>"Matcher::Substring"</Code>" (the default) finds the search text anywhere, "<Code inline=true>"Matcher::Prefix"</Code>" only at the start and "
+            <Code inline=true>"Matcher::Fuzzy"</Code>" finds its characters in order, so that \"frnc\" matches \"France\". "
+            "Any other strategy can be plugged in using "<Code inline=true>"Matcher::Custom"</Code>"."
+        </P>
+
+        <P>
+            "Matching options are sorted by relevance: Matches at the start of the text or of a word and matches of consecutive characters come first. "
+            "Render options using "<Code inline=true>"render_match"</Code>" to also receive the "<Code inline=true>"Match"</Code>", "
+            "whose ranges can be highlighted using the "<Code inline=true>"<Highlight>"</Code>" component."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                view! {
+                    <OptionalSelect
+                        options=COUNTRIES.to_vec()
+                        search_text_provider=move |c: Country| c.name.to_owned()
+                        matcher=Matcher::Fuzzy
+                        render_option=move |c: Country| c.name
+                        render_match=move |(c, found): (Country, Match)| view! {
+                            <Highlight text=c.name ranges=found.ranges/>
+                        }
+                        selected=selected_country
+                        set_selected=set_selected_country
+                        allow_deselect=true
+                    />
+                }
+            "#)}
+        </Code>
+
+        <OptionalSelect
+            options=COUNTRIES.to_vec()
+            search_text_provider=move |c: Country| c.name.to_owned()
+            matcher=Matcher::Fuzzy
+            render_option=move |c: Country| c.name
+            render_match=move |(c, found): (Country, Match)| view! {
+                <Highlight text=c.name ranges=found.ranges/>
+            }
+            selected=selected_country
+            set_selected=set_selected_country
+            allow_deselect=true
+        />
+
+        <P>
+            "A "<Code inline=true>"Matcher"</Code>" can also be used on its own, e.g. by passing it to "<Code inline=true>"<Quicksearch>"</Code>", "
+            "which then filters and sorts the options returned by its query by their labels."
+        </P>
+
+        <H2>"Large option lists"</H2>
+
+        <P>
+            "Only the options scrolled into view are rendered, so that selects with tens of thousands of options open and scroll instantly. "
+            "The height of a single option is measured once and assumed for all others, so options should render with a uniform height."
+        </P>
+
+        <Select
+            options=many_numbers
+            search_text_provider=move |n: u32| n.to_string()
+            render_option=move |n: u32| format!("Option {n}")
+            selected=selected_many
+            set_selected=move |v| set_selected_many.set(v)
+        />
+
+        <H2>"Loading options"</H2>
+
+        <P>
+            "When there are too many options to hold in memory, for example when searching a database table, provide a "<Code inline=true>"loader"</Code>" instead of "<Code inline=true>"options"</Code>". "
+            "The loader receives the search text and asynchronously returns the matching options or an error, which is displayed in the dropdown. "
+            "Options are loaded when the dropdown is opened and whenever the user stopped typing for a moment. "
+            "Responses to outdated search texts are ignored, so that a slow response can never overwrite the options of the current search."
+        </P>
+
+        <P>"This works for "<Code inline=true>"<Select>"</Code>", "<Code inline=true>"<OptionalSelect>"</Code>" and "<Code inline=true>"<Multiselect>"</Code>" alike."</P>
+
+        <Code>
+            {indoc!(r#"
+                async fn search_customers(search: String) -> Result<Vec<Customer>, ServerFnError> {
+                    // Query your backend...
+                }
+
+                view! {
+                    <OptionalSelect
+                        loader=OptionLoader::new(search_customers).debounce(300.0)
+                        search_text_provider=move |c: Customer| c.name
+                        render_option=move |c: Customer| c.name
+                        selected=selected_customer
+                        set_selected=set_selected_customer
+                        allow_deselect=true
+                    />
+                }
+            "#)}
+        </Code>
+
+        <P>"The following select searches the numbers up to one million."</P>
+
+        <OptionalSelect
+            loader=move |search: String| async move {
+                match search.trim().parse::<u32>() {
+                    Ok(prefix) if prefix <= 1_000_000 => {
+                        let prefix = prefix.to_string();
+                        Ok((0..=1_000_000u32).filter(|n| n.to_string().starts_with(&prefix)).take(20).collect())
+                    }
+                    Ok(_) => Err("Numbers only go up to 1000000."),
+                    Err(_) if search.trim().is_empty() => Ok((0..20).collect()),
+                    Err(_) => Err("Please enter a number."),
+                }
+            }
+            search_text_provider=move |n: u32| n.to_string()
+            render_option=move |n: u32| n.to_string()
+            selected=selected_number
+            set_selected=set_selected_number
+            allow_deselect=true
+        />
+
         <H2>"Styling"</H2>
 
         <P>"You may overwrite any of the following CSS variables to meet your styling needs."</P>
@@ -264,6 +492,10 @@ pub fn PageSelect() -> impl IntoView {
                 --select-search-background-color
                 --select-no-items-color
                 --select-no-items-background-color
+                --select-loading-color
+                --select-load-error-color
+                --select-group-color
+                --select-group-font-weight
                 --select-item-color
                 --select-item-background-color
                 --select-item-padding
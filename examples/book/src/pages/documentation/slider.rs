@@ -172,7 +172,9 @@ pub fn PageSlider() -> impl IntoView {
 
         <P>
             "Slider knobs are keyboard-interactable and can be cycled through using the "<Code inline=true>"Tab"</Code>" key. "
-            "Manipulation of slider knobs using the error keys will come in a future update."
+            "A focused knob is moved using the arrow keys, by one step or, without a step, by one percent of the range. "
+            <Code inline=true>"Home"</Code>" and "<Code inline=true>"End"</Code>" move it to the minimum or maximum. "
+            "In right-to-left layouts (see the "<Code inline=true>"direction"</Code>" prop of "<Code inline=true>"<Root>"</Code>"), the slider is mirrored and the left arrow key increases the value."
         </P>
 
         <H2>"Styling"</H2>
@@ -40,6 +40,50 @@ pub fn PageThemes() -> impl IntoView {
             "This is not mandatory though as you could create your own theme-defining type and your own theme toggle components."
         </P>
 
+        <P>
+            "To also offer following the operating systems color scheme, use the "<Code inline=true>"<ThemeChoiceSwitch>"</Code>". "
+            "While \"System\" is chosen, the theme changes together with the users "<Code inline=true>"prefers-color-scheme"</Code>" setting. "
+            "The choice is persisted, just like a theme chosen explicitly. "
+            "Let "<Code inline=true>"<Root>"</Code>" start out following the system using its "<Code inline=true>"follow_system"</Code>" property."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                <ThemeChoiceSwitch light=LeptonicTheme::Light dark=LeptonicTheme::Dark/>
+            "#)}
+        </Code>
+
+        <P>
+            "Apps offering more than two themes can use the "<Code inline=true>"<ThemeSelect>"</Code>", listing all variants of a theme type deriving "<Code inline=true>"strum::EnumIter"</Code>". "
+            "Implement "<Code inline=true>"label"</Code>" and "<Code inline=true>"is_dark"</Code>" of the "<Code inline=true>"Theme"</Code>" trait to control how themes are listed "
+            "and which "<Code inline=true>"color-scheme"</Code>" "<Code inline=true>"<Root>"</Code>" applies to the document."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                <ThemeSelect<LeptonicTheme> system=true/>
+            "#)}
+        </Code>
+
+        <H2>"Density"</H2>
+
+        <P>
+            "Buttons, inputs, selects, tables, tabs and chips come in a "<Code inline=true>"Comfortable"</Code>" (default) and a "<Code inline=true>"Compact"</Code>" density. "
+            "Set the density of the whole application using the "<Code inline=true>"density"</Code>" property of "<Code inline=true>"<Root>"</Code>", "
+            "or that of any subtree using a "<Code inline=true>"<DensityProvider>"</Code>". "
+            "How much the compact density shrinks components is controlled by the "<Code inline=true>"--density-compact-scale"</Code>" theme variable."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                <DensityProvider density=Density::Compact>
+                    <Table>
+                        // ...
+                    </Table>
+                </DensityProvider>
+            "#)}
+        </Code>
+
         <H2>"Customization"</H2>
 
         <P>
@@ -76,5 +120,27 @@ pub fn PageThemes() -> impl IntoView {
                 }
             "#)}
         </Code>
+
+        <H2>"Runtime overrides"</H2>
+
+        <P>
+            "Variables can also be overridden at runtime, for example with a brand color loaded from an API. "
+            "Pass a (reactive) map of variables to a "<Code inline=true>"<ThemeProvider>"</Code>". "
+            "Nested providers inherit the theme and the variables of their parent, overriding them with their own."
+        </P>
+
+        <Code>
+            {indoc!(r#"
+                let (brand_color, set_brand_color) = create_signal(RGB8::from((0x0b, 0x53, 0x94)));
+
+                view! {
+                    <ThemeProvider<LeptonicTheme> variables=Signal::derive(move || Palette::from_seed(brand_color.get()).variables())>
+                        <ThemeProvider<LeptonicTheme> variables=ThemeVariables::from([("--button-border-radius".to_owned(), "0".to_owned())])>
+                            // ...
+                        </ThemeProvider<LeptonicTheme>>
+                    </ThemeProvider<LeptonicTheme>>
+                }
+            "#)}
+        </Code>
     }
 }
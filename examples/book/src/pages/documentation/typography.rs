@@ -66,6 +66,9 @@ pub fn PageTypography() -> impl IntoView {
                 --typography-inline-code-margin
                 --typography-inline-code-padding
                 --typography-inline-code-line-height
+                --typography-highlight-color
+                --typography-highlight-background-color
+                --typography-highlight-font-weight
             "#)}
         </Code>
     }
@@ -0,0 +1,286 @@
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+
+use crate::SCSS_DIR;
+
+/// The bundled theme a custom theme starts out from.
+/// Every variable not explicitly overridden keeps the value defined by this theme.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BaseTheme {
+    #[default]
+    Light,
+    Dark,
+    /// Black background, white text and bright accent colors. All text meets the WCAG 2 AA contrast requirement.
+    HighContrast,
+}
+
+impl BaseTheme {
+    /// All bundled themes, in the order in which they are imported.
+    pub const ALL: [BaseTheme; 3] = [BaseTheme::Light, BaseTheme::Dark, BaseTheme::HighContrast];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BaseTheme::Light => "light",
+            BaseTheme::Dark => "dark",
+            BaseTheme::HighContrast => "high-contrast",
+        }
+    }
+
+    /// The SCSS source of this theme, as bundled with this crate.
+    pub fn source(&self) -> &'static str {
+        SCSS_DIR
+            .get_file(format!("themes/{}.scss", self.name()))
+            .and_then(|file| file.contents_utf8())
+            .expect("bundled theme to exist")
+    }
+}
+
+/// Defines a custom theme, rendered as an additional `[data-theme="<name>"]` block.
+///
+/// ```
+/// use leptonic_theme::{BaseTheme, ThemeBuilder};
+///
+/// let corporate = ThemeBuilder::new("corporate")
+///     .based_on(BaseTheme::Light)
+///     .brand_color("#0b5394")
+///     .danger_color("#c0392b")
+///     .font_family("\"Inter\", sans-serif")
+///     .variable("--button-border-radius", "0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeBuilder {
+    name: String,
+    base: BaseTheme,
+    brand_color: Option<String>,
+    primary_color: Option<String>,
+    secondary_color: Option<String>,
+    info_color: Option<String>,
+    success_color: Option<String>,
+    warn_color: Option<String>,
+    danger_color: Option<String>,
+    greys: [Option<String>; 7],
+    font_family: Option<String>,
+    overrides: IndexMap<String, String>,
+}
+
+impl ThemeBuilder {
+    /// Creates a theme selectable through `data-theme="<name>"`.
+    /// The name must only consist of ASCII alphanumerics, `-` and `_`, as it is used in selectors and file names.
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        assert!(
+            is_valid_theme_name(&name),
+            "Theme name '{name}' is invalid. Only ASCII alphanumerics, '-' and '_' are allowed."
+        );
+        Self {
+            name,
+            base: BaseTheme::default(),
+            brand_color: None,
+            primary_color: None,
+            secondary_color: None,
+            info_color: None,
+            success_color: None,
+            warn_color: None,
+            danger_color: None,
+            greys: Default::default(),
+            font_family: None,
+            overrides: IndexMap::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn base(&self) -> BaseTheme {
+        self.base
+    }
+
+    pub fn based_on(mut self, base: BaseTheme) -> Self {
+        self.base = base;
+        self
+    }
+
+    pub fn brand_color(mut self, color: impl Into<String>) -> Self {
+        self.brand_color = Some(color.into());
+        self
+    }
+
+    pub fn primary_color(mut self, color: impl Into<String>) -> Self {
+        self.primary_color = Some(color.into());
+        self
+    }
+
+    pub fn secondary_color(mut self, color: impl Into<String>) -> Self {
+        self.secondary_color = Some(color.into());
+        self
+    }
+
+    pub fn info_color(mut self, color: impl Into<String>) -> Self {
+        self.info_color = Some(color.into());
+        self
+    }
+
+    pub fn success_color(mut self, color: impl Into<String>) -> Self {
+        self.success_color = Some(color.into());
+        self
+    }
+
+    pub fn warn_color(mut self, color: impl Into<String>) -> Self {
+        self.warn_color = Some(color.into());
+        self
+    }
+
+    pub fn danger_color(mut self, color: impl Into<String>) -> Self {
+        self.danger_color = Some(color.into());
+        self
+    }
+
+    /// Sets `--grey-<index>`. The grey ramp ranges from `0` (brightest) to `6` (darkest).
+    pub fn grey(mut self, index: usize, color: impl Into<String>) -> Self {
+        assert!(index < 7, "Grey index must be in 0..=6, got {index}.");
+        self.greys[index] = Some(color.into());
+        self
+    }
+
+    /// Sets the whole grey ramp, from brightest to darkest.
+    pub fn greys(mut self, greys: [&str; 7]) -> Self {
+        self.greys = greys.map(|grey| Some(grey.to_owned()));
+        self
+    }
+
+    pub fn font_family(mut self, font_family: impl Into<String>) -> Self {
+        self.font_family = Some(font_family.into());
+        self
+    }
+
+    /// Overrides any theme variable, e.g. `--button-border-radius`. The leading `--` may be omitted.
+    /// Variables unknown to the base theme are appended to the generated block.
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        let name = match name.starts_with("--") {
+            true => name,
+            false => format!("--{name}"),
+        };
+        self.overrides.insert(name, value.into());
+        self
+    }
+
+    /// All variables explicitly set on this builder, in the order in which they will be applied.
+    pub fn variables(&self) -> IndexMap<String, String> {
+        let mut variables = IndexMap::new();
+        let typed = [
+            ("--brand-color", &self.brand_color),
+            ("--primary-color", &self.primary_color),
+            ("--secondary-color", &self.secondary_color),
+            ("--info-color", &self.info_color),
+            ("--success-color", &self.success_color),
+            ("--warn-color", &self.warn_color),
+            ("--danger-color", &self.danger_color),
+            ("--font-family", &self.font_family),
+        ];
+        for (name, value) in typed {
+            if let Some(value) = value {
+                variables.insert(name.to_owned(), value.clone());
+            }
+        }
+        for (i, grey) in self.greys.iter().enumerate() {
+            if let Some(grey) = grey {
+                variables.insert(format!("--grey-{i}"), grey.clone());
+            }
+        }
+        for (name, value) in &self.overrides {
+            variables.insert(name.clone(), value.clone());
+        }
+        variables
+    }
+
+    /// Renders the SCSS block defining this theme.
+    pub fn build(&self) -> String {
+        let mut variables = self.variables();
+        let source = self.base.source();
+        let lines = source.lines().collect::<Vec<_>>();
+        let closing = lines
+            .iter()
+            .rposition(|line| line.trim() == "}")
+            .unwrap_or(lines.len());
+
+        let mut out = String::with_capacity(source.len());
+        for (i, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("[data-theme=") {
+                writeln!(out, "[data-theme=\"{}\"] {{", self.name).unwrap();
+                continue;
+            }
+            if i == closing {
+                for (name, value) in variables.drain(..) {
+                    writeln!(out, "    {name}: {value};").unwrap();
+                }
+            }
+            if let Some(name) = declared_variable(trimmed) {
+                if let Some(value) = variables.shift_remove(name) {
+                    let indent = &line[..line.len() - trimmed.len()];
+                    writeln!(out, "{indent}{name}: {value};").unwrap();
+                    continue;
+                }
+            }
+            out.push_str(line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Returns the name of the custom property declared on this (trimmed) line, if any.
+pub(crate) fn declared_variable(line: &str) -> Option<&str> {
+    if !line.starts_with("--") {
+        return None;
+    }
+    line.split_once(':').map(|(name, _)| name.trim_end())
+}
+
+fn is_valid_theme_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BaseTheme, ThemeBuilder};
+
+    #[test]
+    fn build_replaces_selector_and_overridden_variables() {
+        let scss = ThemeBuilder::new("corporate")
+            .brand_color("#0b5394")
+            .grey(3, "#999999")
+            .build();
+        assert!(scss.starts_with("[data-theme=\"corporate\"] {\n"));
+        assert!(scss.contains("    --brand-color: #0b5394;\n"));
+        assert!(scss.contains("    --grey-3: #999999;\n"));
+        assert!(!scss.contains("--brand-color: #e66956;"));
+        assert!(scss.contains("--secondary-color: #9d9b9b;"));
+    }
+
+    #[test]
+    fn build_respects_base_theme() {
+        let scss = ThemeBuilder::new("night").based_on(BaseTheme::Dark).build();
+        assert!(scss.contains("--secondary-color: #6c6c6c;"));
+    }
+
+    #[test]
+    fn build_appends_unknown_variables() {
+        let scss = ThemeBuilder::new("custom")
+            .variable("my-extra", "1em")
+            .build();
+        assert!(scss.trim_end().ends_with("--my-extra: 1em;\n}"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_invalid_names() {
+        ThemeBuilder::new("my theme");
+    }
+}
@@ -0,0 +1,864 @@
+//! Colors in various color spaces, convertible into each other using `From`.
+//!
+//! All conversions go through (unclamped) sRGB in double precision and therefore round trip,
+//! converting into `RGB8` rounds to the nearest representable color.
+//!
+//! Colors can be parsed from and formatted as CSS color strings, see `FromStr` and `Display`.
+//! `RGB8` offers WCAG contrast checks and all spaces can be mixed, see `Interpolate`.
+
+mod format;
+mod math;
+mod named;
+mod parse;
+#[cfg(feature = "serde")]
+mod serialize;
+
+pub use math::{Interpolate, WCAG_AA, WCAG_AAA};
+pub use parse::{parse_color, ParseColorError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorSpace {
+    HSV(HSV),
+    HSL(HSL),
+    HWB(HWB),
+    CMYK(CMYK),
+    RGB8(RGB8),
+    RGBA8(RGBA8),
+    LinearRGB(LinearRGB),
+    Lab(Lab),
+    LCH(LCH),
+    OKLab(OKLab),
+    OKLCH(OKLCH),
+}
+
+/// Hue in degrees, saturation and value in range 0..=1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HSV {
+    pub hue: f64,
+    pub saturation: f64,
+    pub value: f64,
+}
+
+impl HSV {
+    /// Hue starting at red (0.0 degrees) with full saturation (1.0) and full value (1.0).
+    /// This is a sensitive way to construct a new HSV value.
+    pub fn new() -> Self {
+        Self {
+            hue: 0.0,
+            saturation: 1.0,
+            value: 1.0,
+        }
+    }
+
+    pub fn from_hue_fully_saturated(hue: f64) -> Self {
+        Self {
+            hue,
+            saturation: 1.0,
+            value: 1.0,
+        }
+    }
+
+    pub fn with_hue(self, hue: f64) -> Self {
+        Self {
+            hue,
+            saturation: self.saturation,
+            value: self.value,
+        }
+    }
+
+    pub fn with_saturation(self, saturation: f64) -> Self {
+        Self {
+            hue: self.hue,
+            saturation,
+            value: self.value,
+        }
+    }
+
+    pub fn with_value(self, value: f64) -> Self {
+        Self {
+            hue: self.hue,
+            saturation: self.saturation,
+            value,
+        }
+    }
+}
+
+impl Default for HSV {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HSV {
+    pub fn into_rgb8(self) -> RGB8 {
+        RGB8::from(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RGB8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl RGB8 {
+    pub fn new() -> RGB8 {
+        Self { r: 0, g: 0, b: 0 }
+    }
+
+    pub fn into_hsv(self) -> HSV {
+        HSV::from(self)
+    }
+}
+
+impl Default for RGB8 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::LowerHex for RGB8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:02x}", self.r))?;
+        f.write_fmt(format_args!("{:02x}", self.g))?;
+        f.write_fmt(format_args!("{:02x}", self.b))
+    }
+}
+
+impl std::fmt::UpperHex for RGB8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:02X}", self.r))?;
+        f.write_fmt(format_args!("{:02X}", self.g))?;
+        f.write_fmt(format_args!("{:02X}", self.b))
+    }
+}
+
+impl std::fmt::Display for RGB8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("#{:X}", self))
+    }
+}
+
+impl From<(u8, u8, u8)> for RGB8 {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self { r, g, b }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RGBA8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl RGBA8 {
+    pub fn new() -> RGBA8 {
+        Self {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        }
+    }
+
+    pub fn from_rgb8(rgb: RGB8, a: u8) -> Self {
+        let RGB8 { r, g, b } = rgb;
+        Self { r, g, b, a }
+    }
+
+    /// The color without its alpha channel.
+    pub fn rgb8(self) -> RGB8 {
+        RGB8 {
+            r: self.r,
+            g: self.g,
+            b: self.b,
+        }
+    }
+
+    /// The alpha channel in range 0..=1.
+    pub fn alpha(self) -> f64 {
+        self.a as f64 / 255.0
+    }
+}
+
+impl Default for RGBA8 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for RGBA8 {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+/// Fully opaque.
+impl From<RGB8> for RGBA8 {
+    fn from(rgb: RGB8) -> Self {
+        Self::from_rgb8(rgb, 255)
+    }
+}
+
+/// Hue in degrees, saturation and lightness in range 0..=1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HSL {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+/// Hue in degrees, whiteness and blackness in range 0..=1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HWB {
+    pub hue: f64,
+    pub whiteness: f64,
+    pub blackness: f64,
+}
+
+/// All components in range 0..=1. This is a naive conversion, not taking any color profile into account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CMYK {
+    pub cyan: f64,
+    pub magenta: f64,
+    pub yellow: f64,
+    pub key: f64,
+}
+
+/// sRGB without gamma encoding, channels in range 0..=1. Mixing colors in this space is physically correct.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearRGB {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+/// CIE Lab relative to the D50 white point, as used by CSS. Lightness in range 0..=100.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub lightness: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// The polar form of `Lab`. Hue in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LCH {
+    pub lightness: f64,
+    pub chroma: f64,
+    pub hue: f64,
+}
+
+/// Oklab, a perceptually uniform color space. Lightness in range 0..=1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OKLab {
+    pub lightness: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// The polar form of `OKLab`. Hue in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OKLCH {
+    pub lightness: f64,
+    pub chroma: f64,
+    pub hue: f64,
+}
+
+/// A color of any space together with an alpha channel in range 0..=1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alpha<C> {
+    pub color: C,
+    pub alpha: f64,
+}
+
+impl<C> Alpha<C> {
+    pub fn new(color: C, alpha: f64) -> Self {
+        Self { color, alpha }
+    }
+
+    pub fn opaque(color: C) -> Self {
+        Self::new(color, 1.0)
+    }
+
+    /// Converts the color into another space, keeping the alpha channel.
+    pub fn convert<T: From<C>>(self) -> Alpha<T> {
+        Alpha {
+            color: T::from(self.color),
+            alpha: self.alpha,
+        }
+    }
+}
+
+impl<C: From<RGB8>> From<RGBA8> for Alpha<C> {
+    fn from(rgba: RGBA8) -> Self {
+        Self::new(C::from(rgba.rgb8()), rgba.alpha())
+    }
+}
+
+impl<C> From<Alpha<C>> for RGBA8
+where
+    RGB8: From<C>,
+{
+    fn from(color: Alpha<C>) -> Self {
+        RGBA8::from_rgb8(
+            RGB8::from(color.color),
+            (color.alpha.clamp(0.0, 1.0) * 255.0).round() as u8,
+        )
+    }
+}
+
+/// Conversion from and to gamma encoded sRGB with channels (nominally) in range 0..=1.
+/// All color spaces convert into each other through this representation.
+/// Values are not clamped, so that colors outside of the sRGB gamut survive conversions between the wider spaces.
+trait Srgb {
+    fn to_srgb(self) -> [f64; 3];
+    fn from_srgb(rgb: [f64; 3]) -> Self;
+}
+
+impl Srgb for RGB8 {
+    fn to_srgb(self) -> [f64; 3] {
+        [self.r, self.g, self.b].map(|channel| channel as f64 / 255.0)
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        let [r, g, b] = rgb.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+        RGB8 { r, g, b }
+    }
+}
+
+/// The hue (in degrees) shared by HSV, HSL and HWB, together with the largest and smallest channel.
+fn hue_max_min([r, g, b]: [f64; 3]) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (hue.rem_euclid(360.0), max, min)
+}
+
+impl Srgb for HSV {
+    fn to_srgb(self) -> [f64; 3] {
+        let hue = self.hue.rem_euclid(360.0);
+        let f = |n: f64| {
+            let k = (n + hue / 60.0) % 6.0;
+            self.value - self.value * self.saturation * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+        [f(5.0), f(3.0), f(1.0)]
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        let (hue, max, min) = hue_max_min(rgb);
+        HSV {
+            hue,
+            saturation: match max == 0.0 {
+                true => 0.0,
+                false => (max - min) / max,
+            },
+            value: max,
+        }
+    }
+}
+
+impl Srgb for HSL {
+    fn to_srgb(self) -> [f64; 3] {
+        let hue = self.hue.rem_euclid(360.0);
+        let a = self.saturation * self.lightness.min(1.0 - self.lightness);
+        let f = |n: f64| {
+            let k = (n + hue / 30.0) % 12.0;
+            self.lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+        [f(0.0), f(8.0), f(4.0)]
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        let (hue, max, min) = hue_max_min(rgb);
+        let lightness = (max + min) / 2.0;
+        HSL {
+            hue,
+            saturation: match lightness == 0.0 || lightness == 1.0 {
+                true => 0.0,
+                false => (max - lightness) / lightness.min(1.0 - lightness),
+            },
+            lightness,
+        }
+    }
+}
+
+impl Srgb for HWB {
+    fn to_srgb(self) -> [f64; 3] {
+        let (whiteness, blackness) = (self.whiteness, self.blackness);
+        if whiteness + blackness >= 1.0 {
+            let grey = whiteness / (whiteness + blackness);
+            return [grey; 3];
+        }
+        HSV {
+            hue: self.hue,
+            saturation: 1.0 - whiteness / (1.0 - blackness),
+            value: 1.0 - blackness,
+        }
+        .to_srgb()
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        let (hue, max, min) = hue_max_min(rgb);
+        HWB {
+            hue,
+            whiteness: min,
+            blackness: 1.0 - max,
+        }
+    }
+}
+
+impl Srgb for CMYK {
+    fn to_srgb(self) -> [f64; 3] {
+        [self.cyan, self.magenta, self.yellow].map(|channel| (1.0 - channel) * (1.0 - self.key))
+    }
+
+    fn from_srgb([r, g, b]: [f64; 3]) -> Self {
+        let key = 1.0 - r.max(g).max(b);
+        let [cyan, magenta, yellow] = match key == 1.0 {
+            true => [0.0; 3],
+            false => [r, g, b].map(|channel| (1.0 - channel - key) / (1.0 - key)),
+        };
+        CMYK {
+            cyan,
+            magenta,
+            yellow,
+            key,
+        }
+    }
+}
+
+fn srgb_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+    let linear = match abs <= 0.04045 {
+        true => abs / 12.92,
+        false => ((abs + 0.055) / 1.055).powf(2.4),
+    };
+    linear.copysign(channel)
+}
+
+fn linear_to_srgb(channel: f64) -> f64 {
+    let abs = channel.abs();
+    let encoded = match abs <= 0.0031308 {
+        true => abs * 12.92,
+        false => 1.055 * abs.powf(1.0 / 2.4) - 0.055,
+    };
+    encoded.copysign(channel)
+}
+
+impl LinearRGB {
+    fn channels(self) -> [f64; 3] {
+        [self.r, self.g, self.b]
+    }
+
+    fn from_channels([r, g, b]: [f64; 3]) -> Self {
+        LinearRGB { r, g, b }
+    }
+}
+
+impl Srgb for LinearRGB {
+    fn to_srgb(self) -> [f64; 3] {
+        self.channels().map(linear_to_srgb)
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        Self::from_channels(rgb.map(srgb_to_linear))
+    }
+}
+
+type Matrix = [[f64; 3]; 3];
+
+fn multiply(matrix: &Matrix, [x, y, z]: [f64; 3]) -> [f64; 3] {
+    matrix.map(|[a, b, c]| a * x + b * y + c * z)
+}
+
+// Matrices as given by CSS Color Module Level 4, chromatically adapting between D65 (sRGB) and D50 (Lab) using the Bradford transform.
+#[rustfmt::skip]
+const LINEAR_SRGB_TO_XYZ_D65: Matrix = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+#[rustfmt::skip]
+const XYZ_D65_TO_LINEAR_SRGB: Matrix = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [0.05563007969699366, -0.20397695888897652, 1.0569715142428786],
+];
+#[rustfmt::skip]
+const D65_TO_D50: Matrix = [
+    [1.0479298208405488, 0.022946793341019088, -0.05019222954313557],
+    [0.029627815688159344, 0.990434484573249, -0.01707382502938514],
+    [-0.009243058152591178, 0.015055144896577895, 0.7518742899580008],
+];
+/// The exact inverse of `D65_TO_D50` (the one given by CSS is only accurate to about 1e-7).
+#[rustfmt::skip]
+const D50_TO_D65: Matrix = [
+    [0.9554733942048977, -0.023098374726038654, 0.06325919498911496],
+    [-0.02836971286639444, 1.0099953374555604, 0.02104147560735432],
+    [0.012314034948960155, -0.02050758481440557, 1.3303659126444372],
+];
+const D50_WHITE: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+
+impl Srgb for Lab {
+    fn to_srgb(self) -> [f64; 3] {
+        let fy = (self.lightness + 16.0) / 116.0;
+        let fx = fy + self.a / 500.0;
+        let fz = fy - self.b / 200.0;
+        let x = match fx.powi(3) > LAB_EPSILON {
+            true => fx.powi(3),
+            false => (116.0 * fx - 16.0) / LAB_KAPPA,
+        };
+        let y = match self.lightness > LAB_KAPPA * LAB_EPSILON {
+            true => fy.powi(3),
+            false => self.lightness / LAB_KAPPA,
+        };
+        let z = match fz.powi(3) > LAB_EPSILON {
+            true => fz.powi(3),
+            false => (116.0 * fz - 16.0) / LAB_KAPPA,
+        };
+        let xyz_d50 = [x * D50_WHITE[0], y * D50_WHITE[1], z * D50_WHITE[2]];
+        let linear = multiply(&XYZ_D65_TO_LINEAR_SRGB, multiply(&D50_TO_D65, xyz_d50));
+        LinearRGB::from_channels(linear).to_srgb()
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        let linear = LinearRGB::from_srgb(rgb).channels();
+        let xyz_d50 = multiply(&D65_TO_D50, multiply(&LINEAR_SRGB_TO_XYZ_D65, linear));
+        let f = |value: f64, white: f64| {
+            let value = value / white;
+            match value > LAB_EPSILON {
+                true => value.cbrt(),
+                false => (LAB_KAPPA * value + 16.0) / 116.0,
+            }
+        };
+        let [fx, fy, fz] = [
+            f(xyz_d50[0], D50_WHITE[0]),
+            f(xyz_d50[1], D50_WHITE[1]),
+            f(xyz_d50[2], D50_WHITE[2]),
+        ];
+        Lab {
+            lightness: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+/// Converts rectangular `a` and `b` coordinates into chroma and hue (in degrees).
+fn to_polar(a: f64, b: f64) -> (f64, f64) {
+    (a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0))
+}
+
+fn from_polar(chroma: f64, hue: f64) -> (f64, f64) {
+    let hue = hue.to_radians();
+    (chroma * hue.cos(), chroma * hue.sin())
+}
+
+impl Srgb for LCH {
+    fn to_srgb(self) -> [f64; 3] {
+        let (a, b) = from_polar(self.chroma, self.hue);
+        Lab {
+            lightness: self.lightness,
+            a,
+            b,
+        }
+        .to_srgb()
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        let lab = Lab::from_srgb(rgb);
+        let (chroma, hue) = to_polar(lab.a, lab.b);
+        LCH {
+            lightness: lab.lightness,
+            chroma,
+            hue,
+        }
+    }
+}
+
+// Forward matrices as published by Björn Ottosson.
+// The inverse matrices were computed from them with exact arithmetic, so that conversions round trip in double precision.
+#[rustfmt::skip]
+const LINEAR_SRGB_TO_LMS: Matrix = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+#[rustfmt::skip]
+const LMS_TO_LINEAR_SRGB: Matrix = [
+    [4.076741661347994, -3.3077115904081933, 0.2309699287294279],
+    [-1.268438004092176, 2.6097574006633715, -0.3413193963102196],
+    [-0.004196086541837109, -0.7034186144594496, 1.7076147009309448],
+];
+#[rustfmt::skip]
+const LMS_TO_OKLAB: Matrix = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+#[rustfmt::skip]
+const OKLAB_TO_LMS: Matrix = [
+    [0.9999999984505198, 0.39633779217376786, 0.2158037580607588],
+    [1.0000000088817609, -0.10556134232365635, -0.06385417477170591],
+    [1.0000000546724108, -0.08948418209496575, -1.2914855378640917],
+];
+
+impl Srgb for OKLab {
+    fn to_srgb(self) -> [f64; 3] {
+        let lms = multiply(&OKLAB_TO_LMS, [self.lightness, self.a, self.b]).map(|it| it.powi(3));
+        LinearRGB::from_channels(multiply(&LMS_TO_LINEAR_SRGB, lms)).to_srgb()
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        let linear = LinearRGB::from_srgb(rgb).channels();
+        let lms = multiply(&LINEAR_SRGB_TO_LMS, linear).map(f64::cbrt);
+        let [lightness, a, b] = multiply(&LMS_TO_OKLAB, lms);
+        OKLab { lightness, a, b }
+    }
+}
+
+impl Srgb for OKLCH {
+    fn to_srgb(self) -> [f64; 3] {
+        let (a, b) = from_polar(self.chroma, self.hue);
+        OKLab {
+            lightness: self.lightness,
+            a,
+            b,
+        }
+        .to_srgb()
+    }
+
+    fn from_srgb(rgb: [f64; 3]) -> Self {
+        let lab = OKLab::from_srgb(rgb);
+        let (chroma, hue) = to_polar(lab.a, lab.b);
+        OKLCH {
+            lightness: lab.lightness,
+            chroma,
+            hue,
+        }
+    }
+}
+
+/// Implements `From` between every pair of the given color spaces.
+macro_rules! impl_conversions {
+    ($first:ident $(, $rest:ident)*) => {
+        $(
+            impl From<$first> for $rest {
+                fn from(color: $first) -> Self {
+                    <$rest as Srgb>::from_srgb(color.to_srgb())
+                }
+            }
+
+            impl From<$rest> for $first {
+                fn from(color: $rest) -> Self {
+                    <$first as Srgb>::from_srgb(color.to_srgb())
+                }
+            }
+        )*
+        impl_conversions!($($rest),*);
+    };
+    () => {};
+}
+
+impl_conversions!(RGB8, HSV, HSL, HWB, CMYK, LinearRGB, Lab, LCH, OKLab, OKLCH);
+
+impl ColorSpace {
+    fn to_srgb(self) -> [f64; 3] {
+        match self {
+            ColorSpace::HSV(color) => color.to_srgb(),
+            ColorSpace::HSL(color) => color.to_srgb(),
+            ColorSpace::HWB(color) => color.to_srgb(),
+            ColorSpace::CMYK(color) => color.to_srgb(),
+            ColorSpace::RGB8(color) => color.to_srgb(),
+            ColorSpace::RGBA8(color) => color.rgb8().to_srgb(),
+            ColorSpace::LinearRGB(color) => color.to_srgb(),
+            ColorSpace::Lab(color) => color.to_srgb(),
+            ColorSpace::LCH(color) => color.to_srgb(),
+            ColorSpace::OKLab(color) => color.to_srgb(),
+            ColorSpace::OKLCH(color) => color.to_srgb(),
+        }
+    }
+}
+
+/// Implements `From<ColorSpace>`, dropping the alpha channel of `ColorSpace::RGBA8`.
+/// Colors already given in the target space are taken as is.
+macro_rules! impl_from_color_space {
+    ($($space:ident),*) => {
+        $(
+            impl From<ColorSpace> for $space {
+                fn from(color: ColorSpace) -> Self {
+                    match color {
+                        ColorSpace::$space(color) => color,
+                        color => <$space as Srgb>::from_srgb(color.to_srgb()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_color_space!(RGB8, HSV, HSL, HWB, CMYK, LinearRGB, Lab, LCH, OKLab, OKLCH);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{Alpha, Lab, LinearRGB, OKLab, Srgb, CMYK, HSL, HSV, HWB, LCH, OKLCH, RGB8, RGBA8};
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3], tolerance: f64) {
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() <= tolerance,
+                "{actual} != {expected}"
+            );
+        }
+    }
+
+    /// Asserts that `rgb` survives a conversion into each color space.
+    macro_rules! assert_round_trips {
+        ($rgb:expr, $($space:ident),*) => {
+            $(
+                assert_eq!(RGB8::from($space::from($rgb)), $rgb, stringify!($space));
+            )*
+        };
+    }
+
+    proptest! {
+        #[test]
+        fn rgb8_round_trips_through_all_spaces(r: u8, g: u8, b: u8) {
+            let rgb = RGB8 { r, g, b };
+            assert_round_trips!(rgb, HSV, HSL, HWB, CMYK, LinearRGB, Lab, LCH, OKLab, OKLCH);
+        }
+
+        #[test]
+        fn srgb_round_trips_in_double_precision(r in 0.0..=1.0f64, g in 0.0..=1.0f64, b in 0.0..=1.0f64) {
+            let rgb = [r, g, b];
+            assert_close(HSV::from_srgb(rgb).to_srgb(), rgb, 1e-9);
+            assert_close(HSL::from_srgb(rgb).to_srgb(), rgb, 1e-9);
+            assert_close(HWB::from_srgb(rgb).to_srgb(), rgb, 1e-9);
+            assert_close(CMYK::from_srgb(rgb).to_srgb(), rgb, 1e-9);
+            assert_close(LinearRGB::from_srgb(rgb).to_srgb(), rgb, 1e-9);
+            assert_close(LCH::from_srgb(rgb).to_srgb(), rgb, 1e-9);
+            assert_close(OKLCH::from_srgb(rgb).to_srgb(), rgb, 1e-9);
+        }
+
+        #[test]
+        fn wide_gamut_colors_survive_conversions(lightness in 1.0..100.0f64, a in -120.0..120.0f64, b in -120.0..120.0f64) {
+            let lab = Lab { lightness, a, b };
+            let back = Lab::from(LCH::from(OKLCH::from(OKLab::from(lab))));
+            assert_close([back.lightness, back.a, back.b], [lightness, a, b], 1e-6);
+        }
+
+        #[test]
+        fn rgba8_keeps_alpha(r: u8, g: u8, b: u8, a: u8) {
+            let rgba = RGBA8 { r, g, b, a };
+            prop_assert_eq!(RGBA8::from(Alpha::<OKLCH>::from(rgba)), rgba);
+            prop_assert_eq!(RGBA8::from(Alpha::<HSL>::from(rgba).convert::<Lab>()), rgba);
+        }
+    }
+
+    #[test]
+    fn converts_known_colors() {
+        let red = RGB8::from((255, 0, 0));
+        assert_eq!(
+            HSL::from(red),
+            HSL {
+                hue: 0.0,
+                saturation: 1.0,
+                lightness: 0.5
+            }
+        );
+        assert_eq!(
+            CMYK::from(red),
+            CMYK {
+                cyan: 0.0,
+                magenta: 1.0,
+                yellow: 1.0,
+                key: 0.0
+            }
+        );
+        let white = RGB8::from((255, 255, 255));
+        let lab = Lab::from(white);
+        assert_close([lab.lightness, lab.a, lab.b], [100.0, 0.0, 0.0], 1e-4);
+        let oklab = OKLab::from(white);
+        assert_close([oklab.lightness, oklab.a, oklab.b], [1.0, 0.0, 0.0], 1e-6);
+        let lch = LCH::from(red);
+        assert_close(
+            [lch.lightness, lch.chroma, lch.hue],
+            [54.29, 106.84, 40.86],
+            0.01,
+        );
+        let oklch = OKLCH::from(red);
+        assert_close(
+            [oklch.lightness, oklch.chroma, oklch.hue],
+            [0.628, 0.2577, 29.234],
+            0.001,
+        );
+    }
+
+    #[test]
+    fn hsv_to_rgb8_rounds() {
+        // Truncating would yield 127.
+        let grey = HSV {
+            hue: 0.0,
+            saturation: 0.0,
+            value: 0.5,
+        };
+        assert_eq!(RGB8::from(grey), RGB8::from((128, 128, 128)));
+    }
+
+    #[test]
+    fn hwb_normalizes_excess_whiteness_and_blackness() {
+        let grey = HWB {
+            hue: 120.0,
+            whiteness: 0.6,
+            blackness: 0.6,
+        };
+        assert_eq!(RGB8::from(grey), RGB8::from((128, 128, 128)));
+    }
+
+    #[test]
+    fn rgb8_to_lower_hex() {
+        let rgb = RGB8 {
+            r: 186,
+            g: 23,
+            b: 241,
+        };
+        assert_eq!(format!("{:x}", rgb).as_str(), "ba17f1");
+    }
+
+    #[test]
+    fn rgb8_to_upper_hex() {
+        let rgb = RGB8 {
+            r: 186,
+            g: 23,
+            b: 241,
+        };
+        assert_eq!(format!("{:X}", rgb).as_str(), "BA17F1");
+    }
+
+    #[test]
+    fn rgb8_display() {
+        let rgb = RGB8 {
+            r: 186,
+            g: 23,
+            b: 241,
+        };
+        assert_eq!(&rgb.to_string(), "#BA17F1");
+    }
+}
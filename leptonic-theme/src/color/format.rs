@@ -0,0 +1,271 @@
+//! Formatting of colors as CSS color strings, which can be parsed again.
+//!
+//! `RGB8` and `RGBA8` are formatted as hex colors, all other spaces use their CSS color function.
+//! `HSV` has no CSS representation and is formatted using `hwb()`.
+
+use std::fmt::{Display, Formatter, Result};
+
+use super::{Alpha, Lab, LinearRGB, OKLab, CMYK, HSL, HSV, HWB, LCH, OKLCH, RGB8, RGBA8};
+
+/// A color which can be written as a CSS color function, e.g. `hsl(120 50% 25%)`.
+pub trait CssFunction {
+    fn name(&self) -> &'static str;
+
+    /// Writes the space separated components, without parentheses.
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result;
+}
+
+fn write_function(color: &impl CssFunction, alpha: Option<f64>, f: &mut Formatter<'_>) -> Result {
+    f.write_str(color.name())?;
+    f.write_str("(")?;
+    color.write_components(f)?;
+    if let Some(alpha) = alpha {
+        f.write_fmt(format_args!(" / {alpha}"))?;
+    }
+    f.write_str(")")
+}
+
+impl CssFunction for RGB8 {
+    fn name(&self) -> &'static str {
+        "rgb"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!("{} {} {}", self.r, self.g, self.b))
+    }
+}
+
+impl CssFunction for HSV {
+    fn name(&self) -> &'static str {
+        "hwb"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        HWB::from(*self).write_components(f)
+    }
+}
+
+impl CssFunction for HSL {
+    fn name(&self) -> &'static str {
+        "hsl"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!(
+            "{} {}% {}%",
+            self.hue,
+            self.saturation * 100.0,
+            self.lightness * 100.0
+        ))
+    }
+}
+
+impl CssFunction for HWB {
+    fn name(&self) -> &'static str {
+        "hwb"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!(
+            "{} {}% {}%",
+            self.hue,
+            self.whiteness * 100.0,
+            self.blackness * 100.0
+        ))
+    }
+}
+
+impl CssFunction for CMYK {
+    fn name(&self) -> &'static str {
+        "device-cmyk"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!(
+            "{} {} {} {}",
+            self.cyan, self.magenta, self.yellow, self.key
+        ))
+    }
+}
+
+impl CssFunction for LinearRGB {
+    fn name(&self) -> &'static str {
+        "color"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!("srgb-linear {} {} {}", self.r, self.g, self.b))
+    }
+}
+
+impl CssFunction for Lab {
+    fn name(&self) -> &'static str {
+        "lab"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!("{} {} {}", self.lightness, self.a, self.b))
+    }
+}
+
+impl CssFunction for LCH {
+    fn name(&self) -> &'static str {
+        "lch"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!(
+            "{} {} {}",
+            self.lightness, self.chroma, self.hue
+        ))
+    }
+}
+
+impl CssFunction for OKLab {
+    fn name(&self) -> &'static str {
+        "oklab"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!("{} {} {}", self.lightness, self.a, self.b))
+    }
+}
+
+impl CssFunction for OKLCH {
+    fn name(&self) -> &'static str {
+        "oklch"
+    }
+
+    fn write_components(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!(
+            "{} {} {}",
+            self.lightness, self.chroma, self.hue
+        ))
+    }
+}
+
+macro_rules! impl_display {
+    ($($space:ident),*) => {
+        $(
+            impl Display for $space {
+                fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                    write_function(self, None, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_display!(HSV, HSL, HWB, CMYK, LinearRGB, Lab, LCH, OKLab, OKLCH);
+
+impl std::fmt::LowerHex for RGBA8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!("{:x}{:02x}", self.rgb8(), self.a))
+    }
+}
+
+impl std::fmt::UpperHex for RGBA8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_fmt(format_args!("{:X}{:02X}", self.rgb8(), self.a))
+    }
+}
+
+/// Formats opaque colors as `#RRGGBB` and all others as `#RRGGBBAA`.
+impl Display for RGBA8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.a {
+            255 => Display::fmt(&self.rgb8(), f),
+            _ => f.write_fmt(format_args!("#{:X}", self)),
+        }
+    }
+}
+
+/// Uses the color function of `C`, appending the alpha value (e.g. `rgb(10 20 30 / 0.5)`) if it is not fully opaque.
+impl<C: CssFunction> Display for Alpha<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let alpha = match self.alpha >= 1.0 {
+            true => None,
+            false => Some(self.alpha),
+        };
+        write_function(&self.color, alpha, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::{Alpha, Lab, LinearRGB, CMYK, HSL, HSV, HWB, OKLCH, RGB8, RGBA8};
+
+    #[test]
+    fn formats_css_colors() {
+        assert_eq!(RGBA8::from((10, 20, 30, 255)).to_string(), "#0A141E");
+        assert_eq!(RGBA8::from((10, 20, 30, 128)).to_string(), "#0A141E80");
+        let hsl = HSL {
+            hue: 120.0,
+            saturation: 0.5,
+            lightness: 0.25,
+        };
+        assert_eq!(hsl.to_string(), "hsl(120 50% 25%)");
+        assert_eq!(
+            Alpha::new(RGB8::from((10, 20, 30)), 0.5).to_string(),
+            "rgb(10 20 30 / 0.5)"
+        );
+        assert_eq!(HSV::new().to_string(), "hwb(0 0% 0%)");
+    }
+
+    /// Asserts that formatting and parsing again yields the original color.
+    macro_rules! assert_round_trips {
+        ($($color:expr),*) => {
+            $(
+                let color = $color;
+                assert_eq!(color.to_string().parse(), Ok(color), "{}", color);
+            )*
+        };
+    }
+
+    #[test]
+    fn formatted_colors_parse_again() {
+        assert_round_trips!(
+            RGB8::from((0xba, 0x17, 0xf1)),
+            RGBA8::from((0xba, 0x17, 0xf1, 0x42)),
+            HSL {
+                hue: 210.5,
+                saturation: 0.25,
+                lightness: 0.75
+            },
+            HWB {
+                hue: 45.0,
+                whiteness: 0.125,
+                blackness: 0.5
+            },
+            CMYK {
+                cyan: 0.1,
+                magenta: 0.2,
+                yellow: 0.3,
+                key: 0.4
+            },
+            LinearRGB {
+                r: 0.1,
+                g: 1.2,
+                b: -0.3
+            },
+            Lab {
+                lightness: 54.29,
+                a: 80.8,
+                b: 69.9
+            },
+            OKLCH {
+                lightness: 0.628,
+                chroma: 0.2577,
+                hue: 29.234
+            },
+            Alpha::new(
+                OKLCH {
+                    lightness: 0.5,
+                    chroma: 0.1,
+                    hue: 300.0
+                },
+                0.3
+            )
+        );
+    }
+}
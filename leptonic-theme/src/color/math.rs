@@ -0,0 +1,377 @@
+//! Color math: WCAG contrast, mixing and adjusting colors.
+
+use super::{
+    srgb_to_linear, Alpha, Lab, LinearRGB, OKLab, Srgb, CMYK, HSL, HSV, HWB, LCH, OKLCH, RGB8,
+    RGBA8,
+};
+
+/// Minimum contrast ratio of normal text, as required by WCAG 2 level AA.
+pub const WCAG_AA: f64 = 4.5;
+/// Minimum contrast ratio of normal text, as required by WCAG 2 level AAA.
+pub const WCAG_AAA: f64 = 7.0;
+
+/// Linear interpolation between two colors of the same space, component by component.
+/// An `amount` of 0.0 yields `self`, an amount of 1.0 yields `other`.
+///
+/// Hues are interpolated along the shorter arc. Hues of achromatic colors are meaningless,
+/// so the hue of the other color is used instead, as done by CSS.
+pub trait Interpolate {
+    fn interpolate(self, other: Self, amount: f64) -> Self;
+}
+
+fn lerp(from: f64, to: f64, amount: f64) -> f64 {
+    from + (to - from) * amount
+}
+
+/// Interpolates hues (in degrees) along the shorter arc. `None` marks a missing (powerless) hue.
+fn lerp_hue(from: Option<f64>, to: Option<f64>, amount: f64) -> f64 {
+    match (from, to) {
+        (Some(from), Some(to)) => {
+            let delta = (to - from + 180.0).rem_euclid(360.0) - 180.0;
+            (from + delta * amount).rem_euclid(360.0)
+        }
+        (Some(hue), None) | (None, Some(hue)) => hue,
+        (None, None) => 0.0,
+    }
+}
+
+/// `Some(hue)` unless the color is achromatic.
+fn hue_if(chromatic: bool, hue: f64) -> Option<f64> {
+    match chromatic {
+        true => Some(hue),
+        false => None,
+    }
+}
+
+impl Interpolate for RGB8 {
+    /// Interpolates the gamma encoded channels, matching `color-mix(in srgb, ...)`.
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        let [r, g, b] = self.to_srgb();
+        let [r2, g2, b2] = other.to_srgb();
+        RGB8::from_srgb([
+            lerp(r, r2, amount),
+            lerp(g, g2, amount),
+            lerp(b, b2, amount),
+        ])
+    }
+}
+
+impl Interpolate for LinearRGB {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        LinearRGB {
+            r: lerp(self.r, other.r, amount),
+            g: lerp(self.g, other.g, amount),
+            b: lerp(self.b, other.b, amount),
+        }
+    }
+}
+
+impl Interpolate for CMYK {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        CMYK {
+            cyan: lerp(self.cyan, other.cyan, amount),
+            magenta: lerp(self.magenta, other.magenta, amount),
+            yellow: lerp(self.yellow, other.yellow, amount),
+            key: lerp(self.key, other.key, amount),
+        }
+    }
+}
+
+impl Interpolate for HSV {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        HSV {
+            hue: lerp_hue(
+                hue_if(self.saturation > 0.0, self.hue),
+                hue_if(other.saturation > 0.0, other.hue),
+                amount,
+            ),
+            saturation: lerp(self.saturation, other.saturation, amount),
+            value: lerp(self.value, other.value, amount),
+        }
+    }
+}
+
+impl Interpolate for HSL {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        HSL {
+            hue: lerp_hue(
+                hue_if(self.saturation > 0.0, self.hue),
+                hue_if(other.saturation > 0.0, other.hue),
+                amount,
+            ),
+            saturation: lerp(self.saturation, other.saturation, amount),
+            lightness: lerp(self.lightness, other.lightness, amount),
+        }
+    }
+}
+
+impl Interpolate for HWB {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        HWB {
+            hue: lerp_hue(
+                hue_if(self.whiteness + self.blackness < 1.0, self.hue),
+                hue_if(other.whiteness + other.blackness < 1.0, other.hue),
+                amount,
+            ),
+            whiteness: lerp(self.whiteness, other.whiteness, amount),
+            blackness: lerp(self.blackness, other.blackness, amount),
+        }
+    }
+}
+
+impl Interpolate for Lab {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        Lab {
+            lightness: lerp(self.lightness, other.lightness, amount),
+            a: lerp(self.a, other.a, amount),
+            b: lerp(self.b, other.b, amount),
+        }
+    }
+}
+
+impl Interpolate for LCH {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        LCH {
+            lightness: lerp(self.lightness, other.lightness, amount),
+            chroma: lerp(self.chroma, other.chroma, amount),
+            hue: lerp_hue(
+                hue_if(self.chroma > 0.0, self.hue),
+                hue_if(other.chroma > 0.0, other.hue),
+                amount,
+            ),
+        }
+    }
+}
+
+impl Interpolate for OKLab {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        OKLab {
+            lightness: lerp(self.lightness, other.lightness, amount),
+            a: lerp(self.a, other.a, amount),
+            b: lerp(self.b, other.b, amount),
+        }
+    }
+}
+
+impl Interpolate for OKLCH {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        OKLCH {
+            lightness: lerp(self.lightness, other.lightness, amount),
+            chroma: lerp(self.chroma, other.chroma, amount),
+            hue: lerp_hue(
+                hue_if(self.chroma > 0.0, self.hue),
+                hue_if(other.chroma > 0.0, other.hue),
+                amount,
+            ),
+        }
+    }
+}
+
+impl<C: Interpolate> Interpolate for Alpha<C> {
+    /// Interpolates the color and its alpha channel independently (not premultiplied).
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        Alpha {
+            color: self.color.interpolate(other.color, amount),
+            alpha: lerp(self.alpha, other.alpha, amount),
+        }
+    }
+}
+
+impl Interpolate for RGBA8 {
+    fn interpolate(self, other: Self, amount: f64) -> Self {
+        RGBA8::from(Alpha::<RGB8>::from(self).interpolate(Alpha::from(other), amount))
+    }
+}
+
+impl RGB8 {
+    /// Relative luminance as defined by WCAG 2, in range 0..=1.
+    pub fn relative_luminance(self) -> f64 {
+        let [r, g, b] = self.to_srgb().map(srgb_to_linear);
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Contrast ratio as defined by WCAG 2, in range 1..=21. The order of both colors does not matter.
+    pub fn contrast_ratio(self, other: RGB8) -> f64 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Whether text of this color is readable on `background` according to WCAG 2 level AA.
+    pub fn is_readable_on(self, background: RGB8) -> bool {
+        self.contrast_ratio(background) >= WCAG_AA
+    }
+
+    /// The text color with the highest contrast on this (background) color.
+    /// Of equally readable candidates, the first one is returned. `None` if there are no candidates.
+    pub fn most_readable(self, candidates: impl IntoIterator<Item = RGB8>) -> Option<RGB8> {
+        candidates
+            .into_iter()
+            .map(|candidate| (candidate, candidate.contrast_ratio(self)))
+            .fold(
+                None,
+                |best: Option<(RGB8, f64)>, (candidate, ratio)| match best {
+                    Some((_, best_ratio)) if best_ratio >= ratio => best,
+                    _ => Some((candidate, ratio)),
+                },
+            )
+            .map(|(best, _)| best)
+    }
+
+    /// Either black or white, whichever is more readable on this (background) color.
+    pub fn readable_text_color(self) -> RGB8 {
+        self.most_readable([RGB8::from((0, 0, 0)), RGB8::from((255, 255, 255))])
+            .expect("candidates not to be empty")
+    }
+
+    /// Mixes this color with `other` in sRGB, like `color-mix(in srgb, ...)`. `amount` is the share of `other`.
+    pub fn mix(self, other: RGB8, amount: f64) -> RGB8 {
+        self.interpolate(other, amount)
+    }
+
+    /// Mixes this color with `other` in the color space `S`, e.g. `OKLab` for perceptually even results.
+    /// `amount` is the share of `other`.
+    pub fn mix_in<S>(self, other: RGB8, amount: f64) -> RGB8
+    where
+        S: Interpolate + From<RGB8>,
+        RGB8: From<S>,
+    {
+        RGB8::from(S::from(self).interpolate(S::from(other), amount))
+    }
+
+    /// Increases the HSL lightness by `amount` (in range 0..=1), like Sass' `lighten()`.
+    pub fn lighten(self, amount: f64) -> RGB8 {
+        RGB8::from(HSV::from(self).lighten(amount))
+    }
+
+    /// Decreases the HSL lightness by `amount` (in range 0..=1), like Sass' `darken()`.
+    pub fn darken(self, amount: f64) -> RGB8 {
+        RGB8::from(HSV::from(self).darken(amount))
+    }
+
+    /// Increases the HSL saturation by `amount` (in range 0..=1), like Sass' `saturate()`.
+    pub fn saturate(self, amount: f64) -> RGB8 {
+        RGB8::from(HSV::from(self).saturate(amount))
+    }
+
+    /// Decreases the HSL saturation by `amount` (in range 0..=1), like Sass' `desaturate()`.
+    pub fn desaturate(self, amount: f64) -> RGB8 {
+        RGB8::from(HSV::from(self).desaturate(amount))
+    }
+}
+
+impl HSV {
+    /// Applies `adjust` to the HSL representation of this color.
+    /// The hue is kept, even if the result is achromatic.
+    fn adjust_hsl(self, adjust: impl FnOnce(&mut HSL)) -> HSV {
+        let mut hsl = HSL::from(self);
+        adjust(&mut hsl);
+        hsl.saturation = hsl.saturation.clamp(0.0, 1.0);
+        hsl.lightness = hsl.lightness.clamp(0.0, 1.0);
+        HSV::from(hsl).with_hue(self.hue)
+    }
+
+    /// Increases the HSL lightness by `amount` (in range 0..=1), like Sass' `lighten()`.
+    pub fn lighten(self, amount: f64) -> HSV {
+        self.adjust_hsl(|hsl| hsl.lightness += amount)
+    }
+
+    /// Decreases the HSL lightness by `amount` (in range 0..=1), like Sass' `darken()`.
+    pub fn darken(self, amount: f64) -> HSV {
+        self.lighten(-amount)
+    }
+
+    /// Increases the HSL saturation by `amount` (in range 0..=1), like Sass' `saturate()`.
+    pub fn saturate(self, amount: f64) -> HSV {
+        self.adjust_hsl(|hsl| hsl.saturation += amount)
+    }
+
+    /// Decreases the HSL saturation by `amount` (in range 0..=1), like Sass' `desaturate()`.
+    pub fn desaturate(self, amount: f64) -> HSV {
+        self.saturate(-amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::{Interpolate, OKLab, HSL, HSV, LCH, RGB8, RGBA8};
+
+    fn rgb(r: u8, g: u8, b: u8) -> RGB8 {
+        RGB8::from((r, g, b))
+    }
+
+    #[test]
+    fn computes_wcag_contrast() {
+        let (black, white) = (rgb(0, 0, 0), rgb(255, 255, 255));
+        assert_eq!(white.relative_luminance(), 1.0);
+        assert_eq!(black.contrast_ratio(white), 21.0);
+        assert_eq!(white.contrast_ratio(black), 21.0);
+        assert!((rgb(0x76, 0x76, 0x76).contrast_ratio(white) - 4.54).abs() < 0.01);
+        assert!(rgb(0x76, 0x76, 0x76).is_readable_on(white));
+        assert!(!rgb(0x77, 0x77, 0x77).is_readable_on(white));
+    }
+
+    #[test]
+    fn picks_readable_text_colors() {
+        assert_eq!(rgb(255, 255, 0).readable_text_color(), rgb(0, 0, 0));
+        assert_eq!(
+            rgb(0x0b, 0x53, 0x94).readable_text_color(),
+            rgb(255, 255, 255)
+        );
+        assert_eq!(rgb(0, 0, 0).most_readable([]), None);
+        // Ties are won by the first candidate.
+        assert_eq!(
+            rgb(10, 20, 30).most_readable([rgb(1, 2, 3), rgb(1, 2, 3)]),
+            Some(rgb(1, 2, 3))
+        );
+    }
+
+    #[test]
+    fn mixes_in_different_spaces() {
+        let (red, blue) = (rgb(255, 0, 0), rgb(0, 0, 255));
+        assert_eq!(red.mix(blue, 0.0), red);
+        assert_eq!(red.mix(blue, 1.0), blue);
+        assert_eq!(red.mix(blue, 0.5), rgb(128, 0, 128));
+        // Red (0°) and blue (240°) meet at magenta (300°) along the shorter arc.
+        assert_eq!(red.mix_in::<HSL>(blue, 0.5), rgb(255, 0, 255));
+        assert_eq!(
+            rgb(0, 0, 0).mix_in::<OKLab>(rgb(255, 255, 255), 0.5),
+            rgb(99, 99, 99)
+        );
+        // The hue of achromatic colors is ignored.
+        let grey = LCH {
+            lightness: 50.0,
+            chroma: 0.0,
+            hue: 0.0,
+        };
+        let green = LCH {
+            lightness: 50.0,
+            chroma: 50.0,
+            hue: 140.0,
+        };
+        assert_eq!(grey.interpolate(green, 0.5).hue, 140.0);
+        assert_eq!(
+            RGBA8::from((0, 0, 0, 0)).interpolate(RGBA8::from((255, 255, 255, 255)), 0.5),
+            RGBA8::from((128, 128, 128, 128))
+        );
+    }
+
+    #[test]
+    fn adjusts_lightness_and_saturation() {
+        let color = rgb(0x0b, 0x53, 0x94);
+        let hsl = |color: RGB8| HSL::from(color);
+        let close = |a: f64, b: f64| (a - b).abs() < 0.005;
+        assert!(close(
+            hsl(color.lighten(0.2)).lightness,
+            hsl(color).lightness + 0.2
+        ));
+        assert!(close(
+            hsl(color.darken(0.1)).lightness,
+            hsl(color).lightness - 0.1
+        ));
+        assert!(close(hsl(color.desaturate(1.0)).saturation, 0.0));
+        assert_eq!(color.lighten(1.0), rgb(255, 255, 255));
+        // Fully desaturated colors keep their hue.
+        let hsv = HSV::from(color);
+        assert_eq!(hsv.desaturate(1.0).saturate(0.5).hue, hsv.hue);
+    }
+}
@@ -0,0 +1,499 @@
+//! Parsing of CSS color strings, following the syntax of CSS Color Module Level 4.
+
+use std::str::FromStr;
+
+use super::{
+    Alpha, ColorSpace, Lab, LinearRGB, OKLab, Srgb, CMYK, HSL, HSV, HWB, LCH, OKLCH, RGB8, RGBA8,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    Empty,
+    /// A `#` not followed by 3, 4, 6 or 8 hexadecimal digits.
+    InvalidHex(String),
+    UnknownName(String),
+    UnknownFunction(String),
+    /// A `color()` function using a color space other than `srgb` or `srgb-linear`.
+    UnsupportedColorSpace(String),
+    /// Unbalanced parentheses or misplaced separators.
+    Syntax(String),
+    ComponentCount {
+        expected: usize,
+        found: usize,
+    },
+    InvalidComponent(String),
+    /// The color is not fully opaque, but was parsed into a type without an alpha channel.
+    UnexpectedAlpha,
+}
+
+impl std::fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseColorError::Empty => f.write_str("Expected a color, found an empty string"),
+            ParseColorError::InvalidHex(hex) => {
+                f.write_fmt(format_args!("'{hex}' is not a valid hex color"))
+            }
+            ParseColorError::UnknownName(name) => {
+                f.write_fmt(format_args!("'{name}' is not a named color"))
+            }
+            ParseColorError::UnknownFunction(name) => {
+                f.write_fmt(format_args!("'{name}()' is not a color function"))
+            }
+            ParseColorError::UnsupportedColorSpace(name) => {
+                f.write_fmt(format_args!("The color space '{name}' is not supported"))
+            }
+            ParseColorError::Syntax(input) => {
+                f.write_fmt(format_args!("'{input}' is not a valid color"))
+            }
+            ParseColorError::ComponentCount { expected, found } => f.write_fmt(format_args!(
+                "Expected {expected} color components, found {found}"
+            )),
+            ParseColorError::InvalidComponent(component) => {
+                f.write_fmt(format_args!("'{component}' is not a valid color component"))
+            }
+            ParseColorError::UnexpectedAlpha => {
+                f.write_str("The color is transparent, but the target type has no alpha channel")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// Parses any supported CSS color into the color space it was given in, together with its alpha value.
+///
+/// Supported are hex colors, named colors, `transparent`, `rgb()`, `rgba()`, `hsl()`, `hsla()`, `hwb()`,
+/// `lab()`, `lch()`, `oklab()`, `oklch()`, `color(srgb ...)`, `color(srgb-linear ...)` and `device-cmyk()`,
+/// in both the legacy comma separated and the modern space separated syntax.
+pub fn parse_color(input: &str) -> Result<Alpha<ColorSpace>, ParseColorError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(ParseColorError::Empty);
+    }
+    let lower = trimmed.to_ascii_lowercase();
+
+    if let Some(hex) = lower.strip_prefix('#') {
+        return parse_hex(hex).ok_or_else(|| ParseColorError::InvalidHex(trimmed.to_owned()));
+    }
+
+    if lower == "transparent" {
+        return Ok(Alpha::new(ColorSpace::RGB8(RGB8::new()), 0.0));
+    }
+
+    match lower.split_once('(') {
+        Some((name, rest)) => {
+            let arguments = rest
+                .strip_suffix(')')
+                .filter(|arguments| !arguments.contains(['(', ')']))
+                .ok_or_else(|| ParseColorError::Syntax(trimmed.to_owned()))?;
+            parse_function(name.trim(), arguments, trimmed)
+        }
+        None => RGB8::from_name(&lower)
+            .map(|rgb| Alpha::opaque(ColorSpace::RGB8(rgb)))
+            .ok_or_else(|| ParseColorError::UnknownName(trimmed.to_owned())),
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Alpha<ColorSpace>> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits = hex.as_bytes();
+    let channel = |index: usize| -> u8 {
+        match digits.len() {
+            3 | 4 => u8::from_str_radix(&hex[index..index + 1], 16).unwrap() * 17,
+            _ => u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).unwrap(),
+        }
+    };
+    let rgb = match digits.len() {
+        3 | 4 | 6 | 8 => RGB8::from((channel(0), channel(1), channel(2))),
+        _ => return None,
+    };
+    let alpha = match digits.len() {
+        4 | 8 => channel(3),
+        _ => 255,
+    };
+    Some(Alpha::new(ColorSpace::RGB8(rgb), alpha as f64 / 255.0))
+}
+
+/// A single component of a color function, before it is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Component {
+    Number(f64),
+    Percentage(f64),
+    /// An angle, converted to degrees.
+    Angle(f64),
+    /// The `none` keyword.
+    None,
+}
+
+impl Component {
+    fn parse(token: &str) -> Result<Self, ParseColorError> {
+        let invalid = || ParseColorError::InvalidComponent(token.to_owned());
+        let number = |number: &str| {
+            number
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .ok_or_else(invalid)
+        };
+        if token == "none" {
+            return Ok(Component::None);
+        }
+        if let Some(percentage) = token.strip_suffix('%') {
+            return number(percentage).map(Component::Percentage);
+        }
+        // "grad" must be checked before "rad".
+        let angles = [
+            ("deg", 1.0),
+            ("grad", 0.9),
+            ("rad", 180.0 / std::f64::consts::PI),
+            ("turn", 360.0),
+        ];
+        for (unit, degrees) in angles {
+            if let Some(angle) = token.strip_suffix(unit) {
+                return number(angle).map(|angle| Component::Angle(angle * degrees));
+            }
+        }
+        number(token).map(Component::Number)
+    }
+
+    /// A number, or a percentage of `reference`.
+    fn number(self, reference: f64, token: &str) -> Result<f64, ParseColorError> {
+        match self {
+            Component::Number(number) => Ok(number),
+            Component::Percentage(percentage) => Ok(percentage / 100.0 * reference),
+            Component::None => Ok(0.0),
+            Component::Angle(_) => Err(ParseColorError::InvalidComponent(token.to_owned())),
+        }
+    }
+
+    /// A hue in degrees. Plain numbers are interpreted as degrees.
+    fn hue(self, token: &str) -> Result<f64, ParseColorError> {
+        match self {
+            Component::Number(degrees) | Component::Angle(degrees) => Ok(degrees),
+            Component::None => Ok(0.0),
+            Component::Percentage(_) => Err(ParseColorError::InvalidComponent(token.to_owned())),
+        }
+    }
+}
+
+/// The parsed arguments of a color function.
+struct Arguments<'a> {
+    tokens: Vec<&'a str>,
+    components: Vec<Component>,
+    alpha: f64,
+}
+
+impl<'a> Arguments<'a> {
+    fn parse(arguments: &'a str, input: &str, expected: usize) -> Result<Self, ParseColorError> {
+        let syntax = || ParseColorError::Syntax(input.to_owned());
+        let (tokens, alpha) = match arguments.contains(',') {
+            // Legacy syntax: `rgb(10, 20, 30, 0.5)`.
+            true => {
+                let mut tokens = arguments.split(',').map(str::trim).collect::<Vec<_>>();
+                if tokens
+                    .iter()
+                    .any(|token| token.is_empty() || token.contains('/'))
+                {
+                    return Err(syntax());
+                }
+                let alpha = match tokens.len() == expected + 1 {
+                    true => tokens.pop(),
+                    false => None,
+                };
+                (tokens, alpha)
+            }
+            // Modern syntax: `rgb(10 20 30 / 50%)`.
+            false => match arguments.split_once('/') {
+                Some((components, alpha)) => {
+                    let alpha = alpha.trim();
+                    if alpha.is_empty() || alpha.contains([' ', '/']) {
+                        return Err(syntax());
+                    }
+                    (components.split_whitespace().collect(), Some(alpha))
+                }
+                None => (arguments.split_whitespace().collect(), None),
+            },
+        };
+        if tokens.len() != expected {
+            return Err(ParseColorError::ComponentCount {
+                expected,
+                found: tokens.len(),
+            });
+        }
+        let components = tokens
+            .iter()
+            .map(|token| Component::parse(token))
+            .collect::<Result<Vec<_>, _>>()?;
+        let alpha = match alpha {
+            Some(token) => Component::parse(token)?.number(1.0, token)?.clamp(0.0, 1.0),
+            None => 1.0,
+        };
+        Ok(Self {
+            tokens,
+            components,
+            alpha,
+        })
+    }
+
+    fn number(&self, index: usize, reference: f64) -> Result<f64, ParseColorError> {
+        self.components[index].number(reference, self.tokens[index])
+    }
+
+    fn hue(&self, index: usize) -> Result<f64, ParseColorError> {
+        self.components[index].hue(self.tokens[index])
+    }
+}
+
+fn parse_function(
+    name: &str,
+    arguments: &str,
+    input: &str,
+) -> Result<Alpha<ColorSpace>, ParseColorError> {
+    let color = |expected: usize| Arguments::parse(arguments, input, expected);
+    let (color, alpha) = match name {
+        "rgb" | "rgba" => {
+            let args = color(3)?;
+            let channel = |index: usize| {
+                args.number(index, 255.0)
+                    .map(|channel| channel.clamp(0.0, 255.0).round() as u8)
+            };
+            let rgb = RGB8::from((channel(0)?, channel(1)?, channel(2)?));
+            (ColorSpace::RGB8(rgb), args.alpha)
+        }
+        "hsl" | "hsla" => {
+            let args = color(3)?;
+            let hsl = HSL {
+                hue: args.hue(0)?,
+                saturation: (args.number(1, 100.0)? / 100.0).clamp(0.0, 1.0),
+                lightness: (args.number(2, 100.0)? / 100.0).clamp(0.0, 1.0),
+            };
+            (ColorSpace::HSL(hsl), args.alpha)
+        }
+        "hwb" => {
+            let args = color(3)?;
+            let hwb = HWB {
+                hue: args.hue(0)?,
+                whiteness: (args.number(1, 100.0)? / 100.0).clamp(0.0, 1.0),
+                blackness: (args.number(2, 100.0)? / 100.0).clamp(0.0, 1.0),
+            };
+            (ColorSpace::HWB(hwb), args.alpha)
+        }
+        "lab" => {
+            let args = color(3)?;
+            let lab = Lab {
+                lightness: args.number(0, 100.0)?.clamp(0.0, 100.0),
+                a: args.number(1, 125.0)?,
+                b: args.number(2, 125.0)?,
+            };
+            (ColorSpace::Lab(lab), args.alpha)
+        }
+        "lch" => {
+            let args = color(3)?;
+            let lch = LCH {
+                lightness: args.number(0, 100.0)?.clamp(0.0, 100.0),
+                chroma: args.number(1, 150.0)?.max(0.0),
+                hue: args.hue(2)?,
+            };
+            (ColorSpace::LCH(lch), args.alpha)
+        }
+        "oklab" => {
+            let args = color(3)?;
+            let oklab = OKLab {
+                lightness: args.number(0, 1.0)?.clamp(0.0, 1.0),
+                a: args.number(1, 0.4)?,
+                b: args.number(2, 0.4)?,
+            };
+            (ColorSpace::OKLab(oklab), args.alpha)
+        }
+        "oklch" => {
+            let args = color(3)?;
+            let oklch = OKLCH {
+                lightness: args.number(0, 1.0)?.clamp(0.0, 1.0),
+                chroma: args.number(1, 0.4)?.max(0.0),
+                hue: args.hue(2)?,
+            };
+            (ColorSpace::OKLCH(oklch), args.alpha)
+        }
+        "color" => {
+            let (space, arguments) = arguments
+                .trim_start()
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| ParseColorError::Syntax(input.to_owned()))?;
+            let args = Arguments::parse(arguments, input, 3)?;
+            let channels = [
+                args.number(0, 1.0)?,
+                args.number(1, 1.0)?,
+                args.number(2, 1.0)?,
+            ];
+            let linear = match space {
+                "srgb" => LinearRGB::from_srgb(channels),
+                "srgb-linear" => {
+                    let [r, g, b] = channels;
+                    LinearRGB { r, g, b }
+                }
+                _ => return Err(ParseColorError::UnsupportedColorSpace(space.to_owned())),
+            };
+            (ColorSpace::LinearRGB(linear), args.alpha)
+        }
+        "device-cmyk" => {
+            let args = color(4)?;
+            let cmyk = CMYK {
+                cyan: args.number(0, 1.0)?.clamp(0.0, 1.0),
+                magenta: args.number(1, 1.0)?.clamp(0.0, 1.0),
+                yellow: args.number(2, 1.0)?.clamp(0.0, 1.0),
+                key: args.number(3, 1.0)?.clamp(0.0, 1.0),
+            };
+            (ColorSpace::CMYK(cmyk), args.alpha)
+        }
+        _ => return Err(ParseColorError::UnknownFunction(name.to_owned())),
+    };
+    Ok(Alpha::new(color, alpha))
+}
+
+/// Implements `FromStr` for color types without an alpha channel, rejecting transparent colors.
+macro_rules! impl_from_str {
+    ($($space:ident),*) => {
+        $(
+            impl FromStr for $space {
+                type Err = ParseColorError;
+
+                fn from_str(input: &str) -> Result<Self, Self::Err> {
+                    let color = parse_color(input)?;
+                    match color.alpha == 1.0 {
+                        true => Ok($space::from(color.color)),
+                        false => Err(ParseColorError::UnexpectedAlpha),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str!(RGB8, HSV, HSL, HWB, CMYK, LinearRGB, Lab, LCH, OKLab, OKLCH);
+
+impl FromStr for RGBA8 {
+    type Err = ParseColorError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_color(input).map(|color| RGBA8::from(color.convert::<RGB8>()))
+    }
+}
+
+impl<C: From<ColorSpace>> FromStr for Alpha<C> {
+    type Err = ParseColorError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_color(input).map(Alpha::convert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_color, ParseColorError};
+    use crate::color::{Alpha, ColorSpace, HSL, LCH, OKLCH, RGB8, RGBA8};
+
+    fn rgba(input: &str) -> RGBA8 {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_hex_and_named_colors() {
+        assert_eq!(rgba("#ba17f1"), RGBA8::from((0xba, 0x17, 0xf1, 255)));
+        assert_eq!(rgba("#BA17F180"), RGBA8::from((0xba, 0x17, 0xf1, 0x80)));
+        assert_eq!(rgba("#fa0"), RGBA8::from((0xff, 0xaa, 0x00, 255)));
+        assert_eq!(rgba("#fa08"), RGBA8::from((0xff, 0xaa, 0x00, 0x88)));
+        assert_eq!(
+            rgba(" RebeccaPurple "),
+            RGBA8::from((0x66, 0x33, 0x99, 255))
+        );
+        assert_eq!(rgba("transparent"), RGBA8::from((0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn parses_legacy_and_modern_syntax() {
+        assert_eq!(rgba("rgb(10 20 30 / 50%)"), RGBA8::from((10, 20, 30, 128)));
+        assert_eq!(
+            rgba("rgba(10, 20, 30, 0.5)"),
+            RGBA8::from((10, 20, 30, 128))
+        );
+        assert_eq!(rgba("rgb(100% 0% none)"), RGBA8::from((255, 0, 0, 255)));
+        assert_eq!(rgba("hsl(120deg 100% 25%)"), RGBA8::from((0, 128, 0, 255)));
+        assert_eq!(
+            rgba("hsla(0.5turn, 100%, 50%, 1)"),
+            RGBA8::from((0, 255, 255, 255))
+        );
+        assert_eq!(rgba("hwb(0 0% 0%)"), RGBA8::from((255, 0, 0, 255)));
+        assert_eq!(rgba("color(srgb 1 0.5 0)"), RGBA8::from((255, 128, 0, 255)));
+        assert_eq!(rgba("device-cmyk(0 1 1 0)"), RGBA8::from((255, 0, 0, 255)));
+    }
+
+    #[test]
+    fn keeps_the_color_space() {
+        assert_eq!(
+            parse_color("oklch(62.8% 0.2577 29.23)").unwrap().color,
+            ColorSpace::OKLCH(OKLCH {
+                lightness: 0.628,
+                chroma: 0.2577,
+                hue: 29.23
+            })
+        );
+        assert_eq!(
+            "lch(54.29 106.84 40.86deg)".parse::<LCH>().unwrap(),
+            LCH {
+                lightness: 54.29,
+                chroma: 106.84,
+                hue: 40.86
+            }
+        );
+        assert_eq!(
+            "lab(54.29 80.8 69.9)".parse::<RGB8>().unwrap(),
+            RGB8::from((255, 0, 0))
+        );
+        let parsed = "hsl(120 50% 25% / 0.25)".parse::<Alpha<HSL>>().unwrap();
+        assert_eq!(parsed.alpha, 0.25);
+    }
+
+    #[test]
+    fn reports_typed_errors() {
+        assert_eq!("".parse::<RGB8>(), Err(ParseColorError::Empty));
+        assert_eq!(
+            "#12345".parse::<RGB8>(),
+            Err(ParseColorError::InvalidHex("#12345".to_owned()))
+        );
+        assert_eq!(
+            "bluish".parse::<RGB8>(),
+            Err(ParseColorError::UnknownName("bluish".to_owned()))
+        );
+        assert_eq!(
+            "cmyk(1 2 3)".parse::<RGB8>(),
+            Err(ParseColorError::UnknownFunction("cmyk".to_owned()))
+        );
+        assert_eq!(
+            "color(display-p3 1 0 0)".parse::<RGB8>(),
+            Err(ParseColorError::UnsupportedColorSpace(
+                "display-p3".to_owned()
+            ))
+        );
+        assert_eq!(
+            "rgb(1 2)".parse::<RGB8>(),
+            Err(ParseColorError::ComponentCount {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "hsl(10% 20% 30%)".parse::<RGB8>(),
+            Err(ParseColorError::InvalidComponent("10%".to_owned()))
+        );
+        assert_eq!(
+            "rgb(1 2 3".parse::<RGB8>(),
+            Err(ParseColorError::Syntax("rgb(1 2 3".to_owned()))
+        );
+        assert_eq!(
+            "rgb(1 2 3 / 0.5)".parse::<RGB8>(),
+            Err(ParseColorError::UnexpectedAlpha)
+        );
+    }
+}
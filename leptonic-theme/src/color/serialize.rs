@@ -0,0 +1,78 @@
+//! (De)serialization of colors as CSS color strings.
+
+use std::str::FromStr;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    format::CssFunction, Alpha, ColorSpace, Lab, LinearRGB, OKLab, CMYK, HSL, HSV, HWB, LCH, OKLCH,
+    RGB8, RGBA8,
+};
+
+fn deserialize_str<'de, D: Deserializer<'de>, T: FromStr>(deserializer: D) -> Result<T, D::Error>
+where
+    T::Err: std::fmt::Display,
+{
+    let css = String::deserialize(deserializer)?;
+    T::from_str(&css).map_err(D::Error::custom)
+}
+
+macro_rules! impl_serde {
+    ($($space:ident),*) => {
+        $(
+            impl Serialize for $space {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $space {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize_str(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+impl_serde!(RGB8, RGBA8, HSV, HSL, HWB, CMYK, LinearRGB, Lab, LCH, OKLab, OKLCH);
+
+impl<C: CssFunction> Serialize for Alpha<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, C: From<ColorSpace>> Deserialize<'de> for Alpha<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::{Alpha, HSL, RGB8, RGBA8};
+
+    #[test]
+    fn serializes_css_strings() {
+        let color = RGBA8::from((0xba, 0x17, 0xf1, 0x80));
+        assert_eq!(serde_json::to_string(&color).unwrap(), "\"#BA17F180\"");
+        assert_eq!(
+            serde_json::from_str::<RGBA8>("\"#BA17F180\"").unwrap(),
+            color
+        );
+        assert_eq!(
+            serde_json::from_str::<Alpha<HSL>>("\"hsl(120 50% 25% / 0.5)\"").unwrap(),
+            Alpha::new(
+                HSL {
+                    hue: 120.0,
+                    saturation: 0.5,
+                    lightness: 0.25
+                },
+                0.5
+            )
+        );
+        let err = serde_json::from_str::<RGB8>("\"bluish\"").unwrap_err();
+        assert!(err.to_string().contains("'bluish' is not a named color"));
+    }
+}
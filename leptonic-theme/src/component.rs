@@ -0,0 +1,266 @@
+use std::fmt::Write;
+
+/// A component stylesheet which can be selected for inclusion in the generated theme.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Component {
+    Grid,
+    Typography,
+    Alert,
+    AppBar,
+    Box,
+    Button,
+    Card,
+    Checkbox,
+    Chip,
+    Collapsible,
+    ColorPicker,
+    DateTime,
+    Drawer,
+    Icon,
+    Kbd,
+    Link,
+    Geometry,
+    Slider,
+    Select,
+    Tabs,
+    Theme,
+    Density,
+    Toast,
+    Toggle,
+    Transition,
+    Pagination,
+    Popover,
+    ProgressBar,
+    Quicksearch,
+    Nav,
+    Modal,
+    Skeleton,
+    Stack,
+    Tooltip,
+    Validation,
+    Input,
+    FieldLabel,
+    Field,
+    Table,
+    TiptapEditor,
+    ImageGallery,
+}
+
+impl Component {
+    /// All components, in the order in which their stylesheets are imported.
+    pub const ALL: [Component; 41] = [
+        Component::Grid,
+        Component::Typography,
+        Component::Alert,
+        Component::AppBar,
+        Component::Box,
+        Component::Button,
+        Component::Card,
+        Component::Checkbox,
+        Component::Chip,
+        Component::Collapsible,
+        Component::ColorPicker,
+        Component::DateTime,
+        Component::Drawer,
+        Component::Icon,
+        Component::Kbd,
+        Component::Link,
+        Component::Geometry,
+        Component::Slider,
+        Component::Select,
+        Component::Tabs,
+        Component::Theme,
+        Component::Density,
+        Component::Toast,
+        Component::Toggle,
+        Component::Transition,
+        Component::Pagination,
+        Component::Popover,
+        Component::ProgressBar,
+        Component::Quicksearch,
+        Component::Nav,
+        Component::Modal,
+        Component::Skeleton,
+        Component::Stack,
+        Component::Tooltip,
+        Component::Validation,
+        Component::Input,
+        Component::FieldLabel,
+        Component::Field,
+        Component::Table,
+        Component::TiptapEditor,
+        Component::ImageGallery,
+    ];
+
+    /// Components rendered by `<Root>`, which every application therefore needs.
+    pub const ROOT: [Component; 4] = [
+        Component::Box,
+        Component::Toast,
+        Component::Modal,
+        Component::Density,
+    ];
+
+    /// Name of this components stylesheet, relative to the `components` directory and without extension.
+    pub fn stylesheet(&self) -> &'static str {
+        match self {
+            Component::Grid => "grid",
+            Component::Typography => "typography",
+            Component::Alert => "alert",
+            Component::AppBar => "app-bar",
+            Component::Box => "box",
+            Component::Button => "button",
+            Component::Card => "card",
+            Component::Checkbox => "checkbox",
+            Component::Chip => "chip",
+            Component::Collapsible => "collapsible",
+            Component::ColorPicker => "color_picker",
+            Component::DateTime => "datetime",
+            Component::Drawer => "drawer",
+            Component::Icon => "icon",
+            Component::Kbd => "kbd",
+            Component::Link => "link",
+            Component::Geometry => "geometry",
+            Component::Slider => "slider",
+            Component::Select => "select",
+            Component::Tabs => "tabs",
+            Component::Theme => "theme",
+            Component::Density => "density",
+            Component::Toast => "toasts",
+            Component::Toggle => "toggle",
+            Component::Transition => "transition",
+            Component::Pagination => "pagination",
+            Component::Popover => "popover",
+            Component::ProgressBar => "progress_bar",
+            Component::Quicksearch => "quicksearch",
+            Component::Nav => "nav",
+            Component::Modal => "modal",
+            Component::Skeleton => "skeleton",
+            Component::Stack => "stack",
+            Component::Tooltip => "tooltip",
+            Component::Validation => "validation",
+            Component::Input => "input",
+            Component::FieldLabel => "field-label",
+            Component::Field => "field",
+            Component::Table => "table",
+            Component::TiptapEditor => "tiptap_editor",
+            Component::ImageGallery => "image_gallery",
+        }
+    }
+
+    /// Components whose styles are required for this component to render correctly, as they are used internally.
+    pub fn dependencies(&self) -> &'static [Component] {
+        match self {
+            Component::Alert => &[Component::Icon],
+            Component::Button => &[Component::Icon],
+            Component::Chip => &[Component::Icon],
+            Component::Collapsible => &[Component::Icon],
+            Component::ColorPicker => &[
+                Component::Slider,
+                Component::Input,
+                Component::Field,
+                Component::Select,
+                Component::Button,
+            ],
+            Component::DateTime => &[Component::Input],
+            Component::Field => &[Component::FieldLabel],
+            Component::Quicksearch => &[Component::Input, Component::Modal, Component::Button],
+            Component::Select => &[Component::Input, Component::Chip, Component::Icon],
+            Component::Slider => &[Component::Popover],
+            Component::Theme => &[Component::Toggle, Component::Button, Component::Select],
+            Component::TiptapEditor => &[Component::Button, Component::Icon],
+            Component::Toast => &[Component::Icon],
+            Component::Toggle => &[Component::Icon],
+            _ => &[],
+        }
+    }
+
+    /// Extends the given components with `Component::ROOT` and all their (transitive) dependencies.
+    /// The result is ordered like `Component::ALL`.
+    pub fn resolve(components: impl IntoIterator<Item = Component>) -> Vec<Component> {
+        let mut included = [false; Component::ALL.len()];
+        let mut pending = Component::ROOT
+            .into_iter()
+            .chain(components)
+            .collect::<Vec<_>>();
+        while let Some(component) = pending.pop() {
+            let index = component.index();
+            if !included[index] {
+                included[index] = true;
+                pending.extend_from_slice(component.dependencies());
+            }
+        }
+        Component::ALL
+            .into_iter()
+            .filter(|component| included[component.index()])
+            .collect()
+    }
+
+    fn index(&self) -> usize {
+        Component::ALL
+            .iter()
+            .position(|it| it == self)
+            .expect("every component to be listed in ALL")
+    }
+}
+
+/// Renders the `themes/builder.scss` stylesheet importing the given components.
+pub(crate) fn builder(components: &[Component]) -> String {
+    let grid = components.contains(&Component::Grid);
+    let mut builder = String::new();
+    if grid {
+        builder.push_str("@use \"../components/grid\";\n\n");
+    }
+    builder.push_str("@import \"../general/reset\";\n");
+    builder.push_str("@import \"../general/helper\";\n");
+    if grid {
+        builder.push_str("@include grid.produce;\n");
+    }
+    for component in components.iter().filter(|it| **it != Component::Grid) {
+        writeln!(
+            builder,
+            "@import \"../components/{}\";",
+            component.stylesheet()
+        )
+        .unwrap();
+    }
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{builder, Component};
+    use crate::SCSS_DIR;
+
+    #[test]
+    fn all_components_reproduce_bundled_builder() {
+        let bundled = SCSS_DIR
+            .get_file("themes/builder.scss")
+            .and_then(|file| file.contents_utf8())
+            .unwrap();
+        assert_eq!(builder(&Component::ALL).trim_end(), bundled.trim_end());
+    }
+
+    #[test]
+    fn every_component_has_a_stylesheet() {
+        for component in Component::ALL {
+            let path = format!("components/{}.scss", component.stylesheet());
+            assert!(SCSS_DIR.get_file(&path).is_some(), "missing {path}");
+        }
+    }
+
+    #[test]
+    fn resolve_includes_root_and_transitive_dependencies_in_import_order() {
+        assert_eq!(
+            Component::resolve([Component::Slider]),
+            vec![
+                Component::Box,
+                Component::Icon,
+                Component::Slider,
+                Component::Density,
+                Component::Toast,
+                Component::Popover,
+                Component::Modal,
+            ]
+        );
+    }
+}
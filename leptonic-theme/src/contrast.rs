@@ -0,0 +1,310 @@
+//! Checks the text colors of all bundled themes against the WCAG 2 AA contrast requirement.
+
+use std::collections::HashMap;
+
+use crate::{
+    color::{Alpha, RGB8, WCAG_AA},
+    validate::variables,
+    BaseTheme,
+};
+
+/// Pairs of the light and dark themes which are known to fail AA.
+/// These are tolerated to not break existing designs. Use the high-contrast theme if AA is required.
+/// Remove an entry as soon as its pair is fixed. The test fails if a listed pair passes.
+const KNOWN_VIOLATIONS: &[(&str, &str)] = &[
+    ("light", "--button-filled-primary-text-color"),
+    ("light", "--button-filled-primary-text-color-hover"),
+    ("light", "--button-filled-success-text-color"),
+    ("light", "--button-filled-success-text-color-hover"),
+    ("light", "--button-outlined-success-text-color"),
+    ("light", "--button-outlined-success-text-color-hover"),
+    ("light", "--button-filled-info-text-color"),
+    ("light", "--button-filled-info-text-color-hover"),
+    ("light", "--button-flat-info-text-color-hover"),
+    ("light", "--button-outlined-info-text-color"),
+    ("light", "--button-outlined-info-text-color-hover"),
+    ("light", "--button-filled-warning-text-color"),
+    ("light", "--button-filled-warning-text-color-hover"),
+    ("light", "--button-outlined-warning-text-color"),
+    ("light", "--button-outlined-warning-text-color-hover"),
+    ("light", "--button-filled-danger-text-color"),
+    ("light", "--button-filled-danger-text-color-hover"),
+    ("light", "--button-flat-danger-text-color-hover"),
+    ("light", "--button-outlined-danger-text-color"),
+    ("light", "--button-outlined-danger-text-color-hover"),
+    ("light", "--alert-primary-color"),
+    ("light", "--alert-info-color"),
+    ("light", "--alert-success-color"),
+    ("light", "--alert-warn-color"),
+    ("light", "--alert-danger-color"),
+    ("light", "--chip-primary-text-color"),
+    ("light", "--chip-primary-text-color-hover"),
+    ("light", "--chip-secondary-text-color"),
+    ("light", "--chip-secondary-text-color-hover"),
+    ("light", "--chip-success-text-color"),
+    ("light", "--chip-success-text-color-hover"),
+    ("light", "--chip-info-text-color"),
+    ("light", "--chip-info-text-color-hover"),
+    ("light", "--chip-warn-text-color"),
+    ("light", "--chip-warn-text-color-hover"),
+    ("light", "--chip-danger-text-color"),
+    ("light", "--chip-danger-text-color-hover"),
+    ("light", "--toast-info-header-color"),
+    ("light", "--toast-info-message-color"),
+    ("light", "--toast-success-header-color"),
+    ("light", "--toast-success-message-color"),
+    ("light", "--toast-warn-header-color"),
+    ("light", "--toast-warn-message-color"),
+    ("light", "--toast-error-header-color"),
+    ("light", "--toast-error-message-color"),
+    ("dark", "--button-filled-primary-text-color"),
+    ("dark", "--button-filled-primary-text-color-hover"),
+    ("dark", "--button-flat-primary-text-color-hover"),
+    ("dark", "--button-filled-success-text-color"),
+    ("dark", "--button-filled-success-text-color-hover"),
+    ("dark", "--button-flat-success-text-color-hover"),
+    ("dark", "--button-filled-info-text-color"),
+    ("dark", "--button-filled-info-text-color-hover"),
+    ("dark", "--button-flat-info-text-color-hover"),
+    ("dark", "--button-filled-warning-text-color"),
+    ("dark", "--button-filled-warning-text-color-hover"),
+    ("dark", "--button-flat-warning-text-color-hover"),
+    ("dark", "--button-filled-danger-text-color"),
+    ("dark", "--button-filled-danger-text-color-hover"),
+    ("dark", "--button-flat-danger-text-color-hover"),
+    ("dark", "--alert-primary-color"),
+    ("dark", "--alert-info-color"),
+    ("dark", "--alert-success-color"),
+    ("dark", "--alert-warn-color"),
+    ("dark", "--alert-danger-color"),
+    ("dark", "--chip-primary-text-color"),
+    ("dark", "--chip-primary-text-color-hover"),
+    ("dark", "--chip-success-text-color"),
+    ("dark", "--chip-success-text-color-hover"),
+    ("dark", "--chip-info-text-color"),
+    ("dark", "--chip-info-text-color-hover"),
+    ("dark", "--chip-warn-text-color"),
+    ("dark", "--chip-warn-text-color-hover"),
+    ("dark", "--chip-danger-text-color"),
+    ("dark", "--chip-danger-text-color-hover"),
+    ("dark", "--toast-info-header-color"),
+    ("dark", "--toast-info-message-color"),
+    ("dark", "--toast-success-header-color"),
+    ("dark", "--toast-success-message-color"),
+    ("dark", "--toast-warn-header-color"),
+    ("dark", "--toast-warn-message-color"),
+    ("dark", "--toast-error-header-color"),
+    ("dark", "--toast-error-message-color"),
+];
+
+const BUTTON_COLORS: [&str; 6] = [
+    "primary",
+    "secondary",
+    "success",
+    "info",
+    "warning",
+    "danger",
+];
+const ALERT_COLORS: [&str; 5] = ["primary", "info", "success", "warn", "danger"];
+const CHIP_COLORS: [&str; 6] = ["primary", "secondary", "success", "info", "warn", "danger"];
+const TOAST_VARIANTS: [&str; 4] = ["info", "success", "warn", "error"];
+
+/// All (foreground, background) variable pairs rendered as text on a background.
+/// Flat and outlined buttons have a transparent background and are rendered on the page, a `<Box>`.
+fn pairs() -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let page = "--box-background-color".to_owned();
+    for color in BUTTON_COLORS {
+        for state in ["", "-hover"] {
+            pairs.push((
+                format!("--button-filled-{color}-text-color{state}"),
+                format!("--button-filled-{color}-background-color{state}"),
+            ));
+            pairs.push((
+                format!("--button-outlined-{color}-text-color{state}"),
+                page.clone(),
+            ));
+        }
+        pairs.push((format!("--button-flat-{color}-text-color"), page.clone()));
+        pairs.push((
+            format!("--button-flat-{color}-text-color-hover"),
+            format!("--button-flat-{color}-background-color-hover"),
+        ));
+    }
+    for color in ALERT_COLORS {
+        pairs.push((
+            format!("--alert-{color}-color"),
+            format!("--alert-{color}-background-color"),
+        ));
+    }
+    for color in CHIP_COLORS {
+        for state in ["", "-hover"] {
+            pairs.push((
+                format!("--chip-{color}-text-color{state}"),
+                format!("--chip-{color}-background-color{state}"),
+            ));
+        }
+    }
+    for variant in TOAST_VARIANTS {
+        for part in ["header", "message"] {
+            pairs.push((
+                format!("--toast-{variant}-{part}-color"),
+                format!("--toast-{variant}-{part}-background-color"),
+            ));
+        }
+    }
+    pairs.push((
+        "--input-color".to_owned(),
+        "--input-background-color".to_owned(),
+    ));
+    pairs
+}
+
+/// Replaces all `var(--name)` references with the referenced value, recursively.
+fn resolve(value: &str, variables: &HashMap<&str, &str>) -> String {
+    let mut resolved = value.to_owned();
+    while let Some(start) = resolved.find("var(") {
+        let end = start
+            + resolved[start..]
+                .find(')')
+                .unwrap_or_else(|| panic!("unterminated var() in '{value}'"));
+        let name = resolved[start + 4..end].trim();
+        let referenced = variables
+            .get(name)
+            .unwrap_or_else(|| panic!("'{value}' references unknown variable '{name}'"));
+        resolved.replace_range(start..=end, referenced);
+    }
+    resolved
+}
+
+/// Parses CSS colors, additionally supporting the `color-mix(in srgb, ...)` used by the bundled themes.
+fn parse_color(value: &str) -> Option<Alpha<RGB8>> {
+    let value = value.trim();
+    if let Some(args) = value
+        .strip_prefix("color-mix(in srgb,")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let (a, b) = split_top_level_comma(args)?;
+        let (a, a_share) = mix_component(a)?;
+        let (b, b_share) = mix_component(b)?;
+        let (a_share, b_share) = match (a_share, b_share) {
+            (None, None) => (0.5, 0.5),
+            (Some(a), None) => (a, 1.0 - a),
+            (None, Some(b)) => (1.0 - b, b),
+            (Some(a), Some(b)) => (a, b),
+        };
+        return Some(mix(a, b, b_share / (a_share + b_share)));
+    }
+    value.parse::<Alpha<RGB8>>().ok()
+}
+
+/// Mixes two colors like `color-mix()` does, using premultiplied alpha.
+fn mix(a: Alpha<RGB8>, b: Alpha<RGB8>, amount: f64) -> Alpha<RGB8> {
+    let alpha = a.alpha * (1.0 - amount) + b.alpha * amount;
+    if alpha == 0.0 {
+        return Alpha::new(a.color, 0.0);
+    }
+    Alpha::new(a.color.mix(b.color, b.alpha * amount / alpha), alpha)
+}
+
+/// The color seen when painting `color` over the opaque `backdrop`.
+fn over(color: Alpha<RGB8>, backdrop: RGB8) -> RGB8 {
+    backdrop.mix(color.color, color.alpha)
+}
+
+fn split_top_level_comma(value: &str) -> Option<(&str, &str)> {
+    let mut depth = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => return Some((&value[..i], &value[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses `<color> <percentage>?` of a `color-mix()` argument.
+fn mix_component(value: &str) -> Option<(Alpha<RGB8>, Option<f64>)> {
+    let value = value.trim();
+    match value.rsplit_once(' ') {
+        Some((color, share)) if share.ends_with('%') => {
+            let share = share.trim_end_matches('%').parse::<f64>().ok()? / 100.0;
+            Some((parse_color(color)?, Some(share)))
+        }
+        _ => Some((parse_color(value)?, None)),
+    }
+}
+
+#[test]
+fn bundled_themes_meet_aa() {
+    let mut failures = Vec::new();
+    let mut fixed = Vec::new();
+    for theme in BaseTheme::ALL {
+        let variables = variables(theme.source())
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let color = |name: &str| {
+            let value = variables
+                .get(name)
+                .unwrap_or_else(|| panic!("{}: '{name}' is not defined", theme.name()));
+            let resolved = resolve(value, &variables);
+            parse_color(&resolved)
+                .unwrap_or_else(|| panic!("{}: '{name}' is no color: '{resolved}'", theme.name()))
+        };
+        // Translucent colors are seen on top of what lies below them, the page lying on a white canvas.
+        let page = over(color("--box-background-color"), RGB8::from((255, 255, 255)));
+        for (foreground, background) in pairs() {
+            let background = over(color(&background), page);
+            let ratio = over(color(&foreground), background).contrast_ratio(background);
+            let known = KNOWN_VIOLATIONS
+                .iter()
+                .any(|(known_theme, known)| *known_theme == theme.name() && *known == foreground);
+            match (ratio >= WCAG_AA, known) {
+                (false, false) => failures.push(format!(
+                    "{}: {foreground} on {background} has a contrast of {ratio:.2}",
+                    theme.name()
+                )),
+                (true, true) => fixed.push(format!("{}: {foreground}", theme.name())),
+                _ => {}
+            }
+        }
+    }
+    assert!(failures.is_empty(), "below AA:\n{}", failures.join("\n"));
+    assert!(
+        fixed.is_empty(),
+        "passing now, remove from KNOWN_VIOLATIONS:\n{}",
+        fixed.join("\n")
+    );
+}
+
+#[test]
+fn high_contrast_has_no_known_violations() {
+    assert!(KNOWN_VIOLATIONS
+        .iter()
+        .all(|(theme, _)| *theme != BaseTheme::HighContrast.name()));
+}
+
+#[test]
+fn parses_theme_colors() {
+    let opaque = |value: &str| parse_color(value).map(|color| over(color, RGB8::new()));
+    assert_eq!(opaque("#fff"), Some(RGB8::from((255, 255, 255))));
+    assert_eq!(
+        opaque("rgb(56, 121, 192)"),
+        Some(RGB8::from((56, 121, 192)))
+    );
+    assert_eq!(
+        opaque("color-mix(in srgb, black 50%, #ffffff)"),
+        Some(RGB8::from((128, 128, 128)))
+    );
+}
+
+#[test]
+fn blends_translucent_colors() {
+    let white = RGB8::from((255, 255, 255));
+    let translucent = parse_color("rgba(0, 0, 0, 0.5)").unwrap();
+    assert_eq!(over(translucent, white), RGB8::from((128, 128, 128)));
+    let mixed = parse_color("color-mix(in srgb, rgba(0, 0, 0, 0) 50%, #ff0000)").unwrap();
+    assert_eq!(mixed, Alpha::new(RGB8::from((255, 0, 0)), 0.5));
+}
@@ -0,0 +1,346 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    path::{Component as PathComponent, Path, PathBuf},
+};
+
+use crate::{write_if_changed, BaseTheme, Component, GenerateError, GenerateOptions, SCSS_DIR};
+
+/// Configures how `compile_css` turns the generated SCSS into CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CssOptions {
+    /// Emit compressed CSS instead of expanded, human readable CSS.
+    pub minify: bool,
+    /// Also produce a source map. Mappings are coarse: Every generated line maps to the stylesheet it originates from.
+    pub source_map: bool,
+}
+
+impl Default for CssOptions {
+    fn default() -> Self {
+        Self {
+            minify: true,
+            source_map: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledCss {
+    pub css: String,
+    /// A source map (version 3) in JSON format. Only present if requested through `CssOptions::source_map`.
+    pub source_map: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileError {
+    /// The stylesheet which failed to compile, e.g. `components/button.scss`.
+    pub stylesheet: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "Could not compile '{}': {}",
+            self.stylesheet, self.message
+        ))
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// A single, independently compilable part of the generated stylesheet.
+struct Unit {
+    /// Path of the originating stylesheet, relative to the SCSS root.
+    source: String,
+    /// SCSS code compiling this unit, as if located in the SCSS root.
+    scss: String,
+}
+
+/// Compiles the bundled stylesheets, including all custom themes, into a single CSS file.
+/// No external Sass toolchain is required.
+pub fn compile_css(
+    options: &GenerateOptions,
+    css_options: CssOptions,
+) -> Result<CompiledCss, CompileError> {
+    let mut fs = OverlayFs::default();
+    for theme in options.themes() {
+        fs.insert(
+            format!("themes/custom/{}.scss", theme.name()),
+            theme.build(),
+        );
+    }
+
+    let units = units(options);
+    let style = match css_options.minify {
+        true => grass::OutputStyle::Compressed,
+        false => grass::OutputStyle::Expanded,
+    };
+
+    let mut css = String::new();
+    let mut lines_per_unit = Vec::with_capacity(units.len());
+    for (i, unit) in units.iter().enumerate() {
+        let entry = format!(".unit-{i}.scss");
+        fs.insert(entry.clone(), unit.scss.clone());
+        let grass_options = grass::Options::default().style(style).fs(&fs);
+        let compiled = grass::from_path(&entry, &grass_options).map_err(|err| CompileError {
+            stylesheet: unit.source.clone(),
+            message: err.to_string(),
+        })?;
+        let compiled = compiled.trim_end();
+        if compiled.is_empty() {
+            lines_per_unit.push(0);
+            continue;
+        }
+        css.push_str(compiled);
+        css.push('\n');
+        lines_per_unit.push(compiled.lines().count());
+    }
+
+    let source_map = css_options.source_map.then(|| {
+        source_map(
+            &units
+                .iter()
+                .map(|unit| unit.source.as_str())
+                .collect::<Vec<_>>(),
+            &lines_per_unit,
+        )
+    });
+
+    Ok(CompiledCss { css, source_map })
+}
+
+/// Compiles the bundled stylesheets into CSS and writes them to the given file.
+/// A requested source map is written next to it, using the `.map` extension.
+///
+/// # Panics
+///
+/// Panics if compilation or writing fails. Use `try_generate_css` to handle errors.
+pub fn generate_css(path: PathBuf, options: GenerateOptions, css_options: CssOptions) {
+    if let Err(err) = try_generate_css(path, &options, css_options) {
+        panic!("Could not generate leptonic CSS: {err}");
+    }
+}
+
+/// Like `generate_css`, but returns an error instead of panicking.
+/// Files are only written if their content changed.
+pub fn try_generate_css(
+    path: PathBuf,
+    options: &GenerateOptions,
+    css_options: CssOptions,
+) -> Result<(), GenerateError> {
+    let compiled = compile_css(options, css_options)?;
+
+    let mut css = compiled.css;
+    if let Some(source_map) = compiled.source_map {
+        let mut map_path = path.clone().into_os_string();
+        map_path.push(".map");
+        let map_path = PathBuf::from(map_path);
+        let map_file_name = map_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        writeln!(css, "/*# sourceMappingURL={map_file_name} */").unwrap();
+        write_if_changed(&map_path, source_map.as_bytes())?;
+    }
+
+    write_if_changed(&path, css.as_bytes())
+}
+
+/// Splits the generated entry into units, one for each imported stylesheet.
+fn units(options: &GenerateOptions) -> Vec<Unit> {
+    let mut units = Vec::new();
+    for path in ["general/reset", "general/helper"] {
+        units.push(Unit {
+            source: format!("{path}.scss"),
+            scss: format!("@import \"{path}\";"),
+        });
+    }
+    for component in options.resolved_components() {
+        let path = format!("components/{}", component.stylesheet());
+        let scss = match component {
+            Component::Grid => format!("@use \"{path}\";\n@include grid.produce;"),
+            _ => format!("@import \"{path}\";"),
+        };
+        units.push(Unit {
+            source: format!("{path}.scss"),
+            scss,
+        });
+    }
+    for theme in BaseTheme::ALL {
+        let path = format!("themes/{}", theme.name());
+        units.push(Unit {
+            source: format!("{path}.scss"),
+            scss: format!("@import \"{path}\";"),
+        });
+    }
+    for theme in options.themes() {
+        let path = format!("themes/custom/{}", theme.name());
+        units.push(Unit {
+            source: format!("{path}.scss"),
+            scss: format!("@import \"{path}\";"),
+        });
+    }
+    units
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            PathComponent::CurDir | PathComponent::RootDir | PathComponent::Prefix(_) => {}
+            PathComponent::ParentDir => {
+                normalized.pop();
+            }
+            PathComponent::Normal(part) => normalized.push(part),
+        }
+    }
+    normalized
+}
+
+/// Serves the bundled stylesheets, overlaid with generated ones, to the Sass compiler.
+#[derive(Debug, Default)]
+struct OverlayFs {
+    generated: HashMap<PathBuf, String>,
+}
+
+impl OverlayFs {
+    fn insert(&mut self, path: impl AsRef<Path>, content: String) {
+        self.generated.insert(normalize(path.as_ref()), content);
+    }
+}
+
+impl grass::Fs for OverlayFs {
+    fn is_dir(&self, path: &Path) -> bool {
+        let path = normalize(path);
+        path.as_os_str().is_empty()
+            || SCSS_DIR.get_dir(&path).is_some()
+            || self
+                .generated
+                .keys()
+                .any(|it| it.starts_with(&path) && it != &path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        let path = normalize(path);
+        self.generated.contains_key(&path) || SCSS_DIR.get_file(&path).is_some()
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+        let path = normalize(path);
+        if let Some(content) = self.generated.get(&path) {
+            return Ok(content.as_bytes().to_vec());
+        }
+        SCSS_DIR
+            .get_file(&path)
+            .map(|file| file.contents().to_vec())
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("{} does not exist", path.display()),
+                )
+            })
+    }
+}
+
+/// Creates a source map mapping each generated line to the first line of the stylesheet it originates from.
+fn source_map(sources: &[&str], lines_per_unit: &[usize]) -> String {
+    let mut mappings = String::new();
+    let mut previous_source = 0;
+    let mut first_line = true;
+    for (source, lines) in lines_per_unit.iter().enumerate() {
+        for _ in 0..*lines {
+            if !first_line {
+                mappings.push(';');
+            }
+            first_line = false;
+            // Generated column, source index, source line and source column. All but the first are relative.
+            encode_vlq(&mut mappings, 0);
+            encode_vlq(&mut mappings, source as i64 - previous_source as i64);
+            encode_vlq(&mut mappings, 0);
+            encode_vlq(&mut mappings, 0);
+            previous_source = source;
+        }
+    }
+
+    let sources = sources
+        .iter()
+        .map(|source| format!("\"{}\"", escape_json(source)))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(r#"{{"version":3,"sources":[{sources}],"names":[],"mappings":"{mappings}"}}"#)
+}
+
+fn encode_vlq(out: &mut String, value: i64) {
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut vlq = match value < 0 {
+        true => ((-value) << 1) | 1,
+        false => value << 1,
+    };
+    loop {
+        let mut digit = vlq & 0b11111;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64[digit as usize] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{compile_css, encode_vlq, normalize, CssOptions};
+    use crate::{GenerateOptions, ThemeBuilder};
+
+    #[test]
+    fn normalizes_relative_paths() {
+        assert_eq!(
+            normalize(Path::new("themes/../components/_grid.scss")),
+            Path::new("components/_grid.scss")
+        );
+        assert_eq!(
+            normalize(Path::new("./themes/light")),
+            Path::new("themes/light")
+        );
+    }
+
+    #[test]
+    fn encodes_vlq() {
+        let mut out = String::new();
+        for value in [0, 1, -1, 16, 123] {
+            encode_vlq(&mut out, value);
+            out.push(',');
+        }
+        assert_eq!(out, "A,C,D,gB,2H,");
+    }
+
+    #[test]
+    fn compiles_bundled_stylesheets_and_custom_themes() {
+        let options =
+            GenerateOptions::new().theme(ThemeBuilder::new("corporate").brand_color("#0b5394"));
+        let compiled = compile_css(
+            &options,
+            CssOptions {
+                minify: true,
+                source_map: true,
+            },
+        )
+        .unwrap();
+        assert!(compiled.css.contains("leptonic-select"));
+        assert!(compiled.css.contains("#0b5394"));
+        assert!(compiled
+            .source_map
+            .unwrap()
+            .contains("\"themes/custom/corporate.scss\""));
+    }
+}
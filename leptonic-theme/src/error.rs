@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum GenerateError {
+    /// Reading, writing or deleting the given path failed.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The given output directory exists but is not a directory.
+    NotADirectory(PathBuf),
+    #[cfg(feature = "css")]
+    Compile(crate::css::CompileError),
+    #[cfg(feature = "tokens")]
+    Tokens(crate::tokens::TokensError),
+    /// A custom theme failed validation while `Validation::Error` was in effect.
+    Validation {
+        theme: String,
+        report: crate::validate::ValidationReport,
+    },
+}
+
+impl GenerateError {
+    pub(crate) fn io(path: impl Into<PathBuf>) -> impl FnOnce(std::io::Error) -> Self {
+        let path = path.into();
+        move |source| GenerateError::Io { path, source }
+    }
+}
+
+impl std::fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerateError::Io { path, source } => {
+                f.write_fmt(format_args!("I/O error at '{}': {source}", path.display()))
+            }
+            GenerateError::NotADirectory(path) => f.write_fmt(format_args!(
+                "'{}' exists but is not a directory",
+                path.display()
+            )),
+            #[cfg(feature = "css")]
+            GenerateError::Compile(err) => std::fmt::Display::fmt(err, f),
+            #[cfg(feature = "tokens")]
+            GenerateError::Tokens(err) => std::fmt::Display::fmt(err, f),
+            GenerateError::Validation { theme, report } => {
+                f.write_fmt(format_args!("Theme '{theme}' is invalid:\n{report}"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GenerateError::Io { source, .. } => Some(source),
+            GenerateError::NotADirectory(_) => None,
+            #[cfg(feature = "css")]
+            GenerateError::Compile(err) => Some(err),
+            #[cfg(feature = "tokens")]
+            GenerateError::Tokens(err) => Some(err),
+            GenerateError::Validation { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "css")]
+impl From<crate::css::CompileError> for GenerateError {
+    fn from(err: crate::css::CompileError) -> Self {
+        GenerateError::Compile(err)
+    }
+}
+
+#[cfg(feature = "tokens")]
+impl From<crate::tokens::TokensError> for GenerateError {
+    fn from(err: crate::tokens::TokensError) -> Self {
+        GenerateError::Tokens(err)
+    }
+}
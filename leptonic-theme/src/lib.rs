@@ -1,35 +1,276 @@
 use include_dir::{include_dir, Dir};
 use indoc::indoc;
-use std::{io::Write, path::PathBuf};
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+};
+
+pub mod builder;
+pub mod color;
+pub mod component;
+#[cfg(test)]
+mod contrast;
+#[cfg(feature = "css")]
+pub mod css;
+pub mod error;
+pub mod palette;
+#[cfg(feature = "tokens")]
+pub mod tokens;
+pub mod validate;
+
+pub use builder::{BaseTheme, ThemeBuilder};
+pub use component::Component;
+#[cfg(feature = "css")]
+pub use css::{compile_css, generate_css, try_generate_css, CompileError, CompiledCss, CssOptions};
+pub use error::GenerateError;
+pub use palette::Palette;
+#[cfg(feature = "tokens")]
+pub use tokens::{export_tokens, parse_tokens, scss_to_tokens, tokens_to_scss, TokensError};
+pub use validate::{validate_theme, validate_theme_file, Validation, ValidationReport};
 
 static SCSS_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/scss");
 
-/// Path must point to a folder which can be deleted and recreated freely!
-pub fn generate(path: PathBuf) {
-    if path.exists() {
-        std::fs::remove_dir_all(&path).unwrap();
+/// How existing content of the output directory is treated.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WriteMode {
+    /// Delete the output directory and write everything from scratch.
+    #[default]
+    Recreate,
+    /// Keep the output directory and only write files whose content changed.
+    /// Unchanged files keep their modification time, so that file watchers are not triggered needlessly.
+    Merge,
+}
+
+/// Configures what `generate_with` writes in addition to the bundled stylesheets.
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    themes: Vec<ThemeBuilder>,
+    components: Option<Vec<Component>>,
+    write_mode: WriteMode,
+    validation: Validation,
+}
+
+impl GenerateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a custom theme. It is written to `themes/custom/<name>.scss` and imported after the built-in themes.
+    pub fn theme(mut self, theme: ThemeBuilder) -> Self {
+        assert!(
+            self.themes.iter().all(|it| it.name() != theme.name())
+                && BaseTheme::ALL.iter().all(|it| it.name() != theme.name()),
+            "A theme named '{}' is already defined.",
+            theme.name()
+        );
+        self.themes.push(theme);
+        self
+    }
+
+    pub fn themes(&self) -> &[ThemeBuilder] {
+        &self.themes
+    }
+
+    /// Restricts the generated stylesheets to the given components and their dependencies.
+    /// All components are included if this is never called.
+    /// The components listed in `Component::ROOT`, which `<Root>` always renders, are always included.
+    pub fn components(mut self, components: impl IntoIterator<Item = Component>) -> Self {
+        self.components
+            .get_or_insert_with(Vec::new)
+            .extend(components);
+        self
+    }
+
+    pub fn write_mode(mut self, write_mode: WriteMode) -> Self {
+        self.write_mode = write_mode;
+        self
     }
-    std::fs::create_dir_all(&path).unwrap();
 
-    SCSS_DIR.extract(&path).unwrap();
+    /// How custom themes are validated against the variables of the bundled themes. Defaults to `Validation::Warn`.
+    pub fn validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
 
-    let mut file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(false)
-        .truncate(true)
-        .open(path.join("leptonic-themes.scss"))
-        .unwrap();
+    /// The included components, with all dependencies resolved.
+    pub fn resolved_components(&self) -> Vec<Component> {
+        match &self.components {
+            Some(components) => Component::resolve(components.iter().copied()),
+            None => Component::ALL.to_vec(),
+        }
+    }
 
-    file.write_all(
-        indoc!(
+    fn entry(&self) -> String {
+        let mut entry = indoc!(
             r#"
             @import "./themes/builder";
             @import "./themes/light";
             @import "./themes/dark";
+            @import "./themes/high-contrast";
             "#
         )
-        .as_bytes(),
+        .to_owned();
+        for theme in &self.themes {
+            writeln!(entry, "@import \"./themes/custom/{}\";", theme.name()).unwrap();
+        }
+        entry
+    }
+}
+
+/// Path must point to a folder which can be deleted and recreated freely!
+pub fn generate(path: PathBuf) {
+    generate_with(path, GenerateOptions::default())
+}
+
+/// Path must point to a folder which can be deleted and recreated freely, unless `WriteMode::Merge` is used!
+///
+/// # Panics
+///
+/// Panics if generation fails. Use `try_generate_with` to handle errors.
+pub fn generate_with(path: PathBuf, options: GenerateOptions) {
+    if let Err(err) = try_generate_with(path, &options) {
+        panic!("Could not generate leptonic theme: {err}");
+    }
+}
+
+/// Like `generate`, but returns an error instead of panicking.
+pub fn try_generate(path: PathBuf) -> Result<(), GenerateError> {
+    try_generate_with(path, &GenerateOptions::default())
+}
+
+/// Like `generate_with`, but returns an error instead of panicking.
+pub fn try_generate_with(path: PathBuf, options: &GenerateOptions) -> Result<(), GenerateError> {
+    validate_themes(options)?;
+
+    if path.exists() && !path.is_dir() {
+        return Err(GenerateError::NotADirectory(path));
+    }
+    if options.write_mode == WriteMode::Recreate && path.exists() {
+        std::fs::remove_dir_all(&path).map_err(GenerateError::io(&path))?;
+    }
+    std::fs::create_dir_all(&path).map_err(GenerateError::io(&path))?;
+
+    extract(&SCSS_DIR, &path)?;
+
+    write_if_changed(
+        &path.join("themes").join("builder.scss"),
+        component::builder(&options.resolved_components()).as_bytes(),
+    )?;
+
+    for theme in &options.themes {
+        write_if_changed(
+            &path
+                .join("themes")
+                .join("custom")
+                .join(format!("{}.scss", theme.name())),
+            theme.build().as_bytes(),
+        )?;
+    }
+
+    write_if_changed(
+        &path.join("leptonic-themes.scss"),
+        options.entry().as_bytes(),
     )
-    .unwrap();
+}
+
+fn validate_themes(options: &GenerateOptions) -> Result<(), GenerateError> {
+    for theme in &options.themes {
+        let report = theme.validate();
+        if report.is_ok() {
+            continue;
+        }
+        match options.validation {
+            Validation::Off => {}
+            Validation::Warn => report.emit_cargo_warnings(theme.name()),
+            Validation::Error => {
+                return Err(GenerateError::Validation {
+                    theme: theme.name().to_owned(),
+                    report,
+                })
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the bundled stylesheets to `base`.
+/// `themes/builder.scss` is skipped, as it is generated for the selected components afterwards.
+/// Writing the bundled one first would modify the file on every run.
+fn extract(dir: &Dir<'_>, base: &Path) -> Result<(), GenerateError> {
+    for file in dir.files() {
+        if file.path() == Path::new("themes/builder.scss") {
+            continue;
+        }
+        write_if_changed(&base.join(file.path()), file.contents())?;
+    }
+    for dir in dir.dirs() {
+        extract(dir, base)?;
+    }
+    Ok(())
+}
+
+/// Writes `content` to `path`, creating missing parent directories.
+/// Does not touch the file if it already has the given content.
+pub(crate) fn write_if_changed(path: &Path, content: &[u8]) -> Result<(), GenerateError> {
+    match std::fs::read(path) {
+        Ok(existing) if existing == content => return Ok(()),
+        Ok(_) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(GenerateError::io(path)(err)),
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(GenerateError::io(parent))?;
+    }
+    std::fs::write(path, content).map_err(GenerateError::io(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{try_generate_with, GenerateError, GenerateOptions, Validation, WriteMode};
+    use crate::ThemeBuilder;
+
+    #[test]
+    fn merge_keeps_foreign_files_and_unchanged_content() {
+        let dir = std::env::temp_dir().join(format!("leptonic-theme-merge-{}", std::process::id()));
+        let options = GenerateOptions::new().write_mode(WriteMode::Merge);
+
+        try_generate_with(dir.clone(), &options).unwrap();
+        std::fs::write(dir.join("foreign.txt"), "keep me").unwrap();
+        let modified = |file: &str| {
+            std::fs::metadata(dir.join(file))
+                .unwrap()
+                .modified()
+                .unwrap()
+        };
+        let entry = modified("leptonic-themes.scss");
+        let builder = modified("themes/builder.scss");
+
+        try_generate_with(dir.clone(), &options).unwrap();
+        assert!(dir.join("foreign.txt").exists());
+        assert_eq!(modified("leptonic-themes.scss"), entry);
+        assert_eq!(modified("themes/builder.scss"), builder);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_non_directory_output() {
+        let file = std::env::temp_dir().join(format!("leptonic-theme-file-{}", std::process::id()));
+        std::fs::write(&file, "").unwrap();
+        let result = try_generate_with(file.clone(), &GenerateOptions::new());
+        assert!(matches!(result, Err(GenerateError::NotADirectory(_))));
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn strict_validation_rejects_misspelled_variables() {
+        let dir =
+            std::env::temp_dir().join(format!("leptonic-theme-strict-{}", std::process::id()));
+        let options = GenerateOptions::new()
+            .validation(Validation::Error)
+            .theme(ThemeBuilder::new("custom").variable("--brand-colr", "#0b5394"));
+        let result = try_generate_with(dir.clone(), &options);
+        assert!(matches!(result, Err(GenerateError::Validation { .. })));
+        assert!(!dir.exists());
+    }
 }
@@ -0,0 +1,254 @@
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+
+use crate::{
+    color::{HSV, RGB8},
+    ThemeBuilder,
+};
+
+/// Hues of the semantic colors, in degrees.
+const INFO_HUE: f64 = 208.0;
+const SUCCESS_HUE: f64 = 120.0;
+const WARN_HUE: f64 = 34.0;
+const DANGER_HUE: f64 = 2.0;
+
+/// Values (brightness) of the bundled grey ramp, from `--grey-0` to `--grey-6`.
+const GREY_VALUES: [f64; 7] = [0.973, 0.925, 0.859, 0.639, 0.467, 0.2, 0.129];
+/// Saturation of the greys when generated from a fully saturated seed color.
+const GREY_SATURATION: f64 = 0.08;
+
+/// Amount of white (tints) or black (shades) mixed into the seed color, per step.
+const MIX_STEP: f64 = 0.15;
+const STEPS: [f64; 5] = [1.0, 2.0, 3.0, 4.0, 5.0];
+
+/// Contrast between the brand color and the brand color modified by `--darker-1` to `--darker-3`.
+/// Reproduces the modifiers of the bundled themes (5%, 7% and 10%) for their brand color.
+const DARKER_CONTRASTS: [f64; 3] = [1.09, 1.13, 1.2];
+/// Contrast between the brand color and the brand color modified by `--brighter-1` to `--brighter-3`.
+const BRIGHTER_CONTRASTS: [f64; 3] = [1.06, 1.08, 1.12];
+/// Upper bound of the modifiers, in percent. Colors close to black or white can not be changed any further.
+const MAX_MODIFIER: u8 = 30;
+
+/// `--std-text-bright` of the bundled themes.
+pub const TEXT_BRIGHT: RGB8 = RGB8 {
+    r: 0xf0,
+    g: 0xf0,
+    b: 0xf0,
+};
+/// `--std-text-dark` of the bundled themes.
+pub const TEXT_DARK: RGB8 = RGB8 {
+    r: 0x1d,
+    g: 0x1d,
+    b: 0x1d,
+};
+
+/// A set of harmonizing colors, derived from a single seed (brand) color.
+///
+/// ```
+/// use leptonic_theme::{color::RGB8, palette::Palette, ThemeBuilder};
+///
+/// let palette = Palette::from_seed(RGB8::from((0x0b, 0x53, 0x94)));
+/// let corporate = ThemeBuilder::new("corporate").palette(&palette);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub brand: RGB8,
+    /// The brand color mixed with increasing amounts of white.
+    pub tints: [RGB8; 5],
+    /// The brand color mixed with increasing amounts of black.
+    pub shades: [RGB8; 5],
+    /// Percent of white mixed in by `--brighter-1` to `--brighter-3`, so that hovered elements visibly change.
+    pub brighter: [u8; 3],
+    /// Percent of black mixed in by `--darker-1` to `--darker-3`.
+    pub darker: [u8; 3],
+    /// Greys slightly tinted with the brand hue, from brightest to darkest.
+    pub greys: [RGB8; 7],
+    pub secondary: RGB8,
+    pub info: RGB8,
+    pub success: RGB8,
+    pub warn: RGB8,
+    pub danger: RGB8,
+}
+
+impl Palette {
+    pub fn from_seed(seed: RGB8) -> Self {
+        let hsv = seed.into_hsv();
+        let hue = hsv.hue.rem_euclid(360.0);
+
+        // Semantic colors keep their well known hues but adopt the intensity of the seed.
+        let semantic = |semantic_hue: f64| {
+            HSV {
+                hue: semantic_hue,
+                saturation: hsv.saturation.clamp(0.55, 0.85),
+                value: hsv.value.clamp(0.7, 0.9),
+            }
+            .into_rgb8()
+        };
+
+        Self {
+            brand: seed,
+            tints: STEPS.map(|step| seed.mix(RGB8::from((255, 255, 255)), step * MIX_STEP)),
+            shades: STEPS.map(|step| seed.mix(RGB8::from((0, 0, 0)), step * MIX_STEP)),
+            brighter: BRIGHTER_CONTRASTS
+                .map(|contrast| modifier(seed, RGB8::from((255, 255, 255)), contrast)),
+            darker: DARKER_CONTRASTS
+                .map(|contrast| modifier(seed, RGB8::from((0, 0, 0)), contrast)),
+            greys: GREY_VALUES.map(|value| {
+                HSV {
+                    hue,
+                    saturation: GREY_SATURATION * hsv.saturation,
+                    value,
+                }
+                .into_rgb8()
+            }),
+            secondary: HSV {
+                hue,
+                saturation: 0.1 * hsv.saturation,
+                value: 0.42,
+            }
+            .into_rgb8(),
+            info: semantic(INFO_HUE),
+            success: semantic(SUCCESS_HUE),
+            warn: semantic(WARN_HUE),
+            danger: semantic(DANGER_HUE),
+        }
+    }
+
+    /// Either `TEXT_BRIGHT` or `TEXT_DARK`, whichever is more readable on the given background.
+    pub fn text_on(background: RGB8) -> RGB8 {
+        background
+            .most_readable([TEXT_BRIGHT, TEXT_DARK])
+            .expect("candidates not to be empty")
+    }
+
+    /// Theme variables defined by this palette.
+    /// Besides the colors themselves, this includes the brightness modifiers and the text colors of filled buttons.
+    pub fn variables(&self) -> IndexMap<String, String> {
+        let mut variables = IndexMap::new();
+        let colors = [
+            ("brand", "primary", self.brand),
+            ("secondary", "secondary", self.secondary),
+            ("info", "info", self.info),
+            ("success", "success", self.success),
+            ("warn", "warning", self.warn),
+            ("danger", "danger", self.danger),
+        ];
+        for (name, _, color) in colors {
+            variables.insert(format!("--{name}-color"), color.to_string());
+        }
+        for (i, grey) in self.greys.iter().enumerate() {
+            variables.insert(format!("--grey-{i}"), grey.to_string());
+        }
+        for (i, percent) in self.brighter.iter().enumerate() {
+            variables.insert(format!("--brighter-{}", i + 1), format!("{percent}%"));
+        }
+        for (i, percent) in self.darker.iter().enumerate() {
+            variables.insert(format!("--darker-{}", i + 1), format!("{percent}%"));
+        }
+        for (_, button, color) in colors {
+            let text = match Self::text_on(color) == TEXT_BRIGHT {
+                true => "var(--std-text-bright)",
+                false => "var(--std-text-dark)",
+            };
+            for suffix in ["", "-hover"] {
+                variables.insert(
+                    format!("--button-filled-{button}-text-color{suffix}"),
+                    text.to_owned(),
+                );
+            }
+        }
+        variables
+    }
+
+    /// Renders all variables as inline CSS declarations, e.g. to be used in a `style` attribute.
+    pub fn style(&self) -> String {
+        let mut style = String::new();
+        for (name, value) in self.variables() {
+            write!(style, "{name}: {value};").unwrap();
+        }
+        style
+    }
+}
+
+/// The smallest percentage of `towards` to mix into `seed` to reach the given contrast to it.
+fn modifier(seed: RGB8, towards: RGB8, contrast: f64) -> u8 {
+    (1..=MAX_MODIFIER)
+        .find(|percent| {
+            seed.contrast_ratio(seed.mix(towards, f64::from(*percent) / 100.0)) >= contrast
+        })
+        .unwrap_or(MAX_MODIFIER)
+}
+
+impl ThemeBuilder {
+    /// Applies all variables of the given palette.
+    pub fn palette(mut self, palette: &Palette) -> Self {
+        for (name, value) in palette.variables() {
+            self = self.variable(name, value);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Palette, TEXT_BRIGHT, TEXT_DARK};
+    use crate::{color::RGB8, ThemeBuilder};
+
+    fn seed() -> RGB8 {
+        RGB8::from((0x0b, 0x53, 0x94))
+    }
+
+    #[test]
+    fn ramps_are_monotonic() {
+        let palette = Palette::from_seed(seed());
+        let value = |color: RGB8| color.into_hsv().value;
+        for pair in palette.greys.windows(2) {
+            assert!(value(pair[0]) > value(pair[1]), "{pair:?}");
+        }
+        for pair in palette.shades.windows(2) {
+            assert!(value(pair[0]) > value(pair[1]), "{pair:?}");
+        }
+        for pair in palette.tints.windows(2) {
+            assert!(pair[0].into_hsv().saturation > pair[1].into_hsv().saturation);
+        }
+    }
+
+    #[test]
+    fn semantic_colors_keep_their_hue() {
+        let palette = Palette::from_seed(seed());
+        let hue = |color: RGB8| color.into_hsv().hue.rem_euclid(360.0);
+        assert!((hue(palette.info) - 208.0).abs() < 2.0);
+        assert!((hue(palette.success) - 120.0).abs() < 2.0);
+        assert!((hue(palette.warn) - 34.0).abs() < 2.0);
+        assert!(hue(palette.danger) < 4.0);
+    }
+
+    #[test]
+    fn modifiers_reproduce_bundled_themes_and_grow_for_dark_seeds() {
+        let bundled = Palette::from_seed(RGB8::from((0xe6, 0x69, 0x56)));
+        assert_eq!(bundled.brighter, [5, 7, 10]);
+        assert_eq!(bundled.darker, [5, 7, 10]);
+
+        let dark = Palette::from_seed(RGB8::from((0x20, 0x10, 0x40)));
+        assert!(dark.darker[0] > bundled.darker[0], "{:?}", dark.darker);
+        assert_eq!(
+            Palette::from_seed(RGB8::from((0, 0, 0))).darker,
+            [30, 30, 30]
+        );
+    }
+
+    #[test]
+    fn picks_readable_text_colors() {
+        assert_eq!(Palette::text_on(RGB8::from((255, 255, 0))), TEXT_DARK);
+        assert_eq!(Palette::text_on(seed()), TEXT_BRIGHT);
+    }
+
+    #[test]
+    fn palette_only_sets_known_variables() {
+        let theme = ThemeBuilder::new("corporate").palette(&Palette::from_seed(seed()));
+        assert!(theme.validate().is_ok());
+        assert_eq!(theme.variables()["--brand-color"], "#0B5394");
+        assert!(theme.variables().contains_key("--darker-3"));
+    }
+}
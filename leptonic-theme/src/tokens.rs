@@ -0,0 +1,280 @@
+//! Conversion between theme variables and the W3C Design Tokens format.
+//!
+//! A variable like `--alert-margin` maps to the token `margin` in the group `alert`.
+//! Values consisting of a single `var(--other-variable)` are exported as aliases, e.g. `{brand.color}`.
+
+use std::{fmt::Write as _, path::Path};
+
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+
+use crate::{validate::variables, BaseTheme, GenerateError, ThemeBuilder};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokensError {
+    /// Path of the offending token or group, e.g. `button.primary`.
+    pub token: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for TokensError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.token.is_empty() {
+            true => f.write_fmt(format_args!("Invalid design tokens: {}", self.message)),
+            false => f.write_fmt(format_args!(
+                "Invalid design token '{}': {}",
+                self.token, self.message
+            )),
+        }
+    }
+}
+
+impl std::error::Error for TokensError {}
+
+/// Exports the variables of a bundled theme as a (pretty printed) design tokens JSON document.
+pub fn export_tokens(theme: BaseTheme) -> String {
+    scss_to_tokens(theme.source())
+}
+
+/// Exports all custom property declarations found in `scss` as a (pretty printed) design tokens JSON document.
+pub fn scss_to_tokens(scss: &str) -> String {
+    let mut root = Map::new();
+    for (name, value) in variables(scss) {
+        let (group, token) = token_path(name);
+        let mut entry = Map::new();
+        entry.insert("$value".to_owned(), Value::String(export_value(value)));
+        if let Some(ty) = infer_type(name, value) {
+            entry.insert("$type".to_owned(), Value::String(ty.to_owned()));
+        }
+        let target = match group {
+            Some(group) => root
+                .entry(group.to_owned())
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .expect("groups to be objects"),
+            None => &mut root,
+        };
+        target.insert(token.to_owned(), Value::Object(entry));
+    }
+    serde_json::to_string_pretty(&Value::Object(root)).expect("serializing JSON values not to fail")
+}
+
+/// Reads a design tokens JSON document and returns the theme variables it defines, in document order.
+/// Nested group names are joined using `-`, so that `{ "button": { "primary": { "color": ... } } }`
+/// defines `--button-primary-color`.
+pub fn parse_tokens(json: &str) -> Result<IndexMap<String, String>, TokensError> {
+    let root = serde_json::from_str::<Value>(json).map_err(|err| TokensError {
+        token: String::new(),
+        message: err.to_string(),
+    })?;
+    let mut variables = IndexMap::new();
+    collect(&root, &mut Vec::new(), &mut variables)?;
+    Ok(variables)
+}
+
+/// Renders a `[data-theme="<name>"]` block declaring exactly the variables defined by the given tokens.
+pub fn tokens_to_scss(name: &str, json: &str) -> Result<String, TokensError> {
+    let mut scss = format!("[data-theme=\"{name}\"] {{\n");
+    for (name, value) in parse_tokens(json)? {
+        writeln!(scss, "    {name}: {value};").unwrap();
+    }
+    scss.push_str("}\n");
+    Ok(scss)
+}
+
+impl ThemeBuilder {
+    /// Overrides all variables defined by the given design tokens JSON document.
+    pub fn tokens(mut self, json: &str) -> Result<Self, TokensError> {
+        for (name, value) in parse_tokens(json)? {
+            self = self.variable(name, value);
+        }
+        Ok(self)
+    }
+
+    /// Like `tokens`, but reads the document from a file.
+    pub fn tokens_file(self, path: impl AsRef<Path>) -> Result<Self, GenerateError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(GenerateError::io(path))?;
+        self.tokens(&json).map_err(GenerateError::from)
+    }
+}
+
+/// Splits `--alert-margin` into the group `alert` and token `margin`.
+fn token_path(variable: &str) -> (Option<&str>, &str) {
+    let name = variable.trim_start_matches("--");
+    match name.split_once('-') {
+        Some((group, token)) => (Some(group), token),
+        None => (None, name),
+    }
+}
+
+fn collect(
+    value: &Value,
+    path: &mut Vec<String>,
+    variables: &mut IndexMap<String, String>,
+) -> Result<(), TokensError> {
+    fn error(path: &[String], message: &str) -> TokensError {
+        TokensError {
+            token: path.join("."),
+            message: message.to_owned(),
+        }
+    }
+
+    let object = value
+        .as_object()
+        .ok_or_else(|| error(path, "expected a group or token object"))?;
+
+    if let Some(value) = object.get("$value") {
+        if path.is_empty() {
+            return Err(error(path, "the document itself can not be a token"));
+        }
+        let value = import_value(value).ok_or_else(|| error(path, "unsupported value"))?;
+        variables.insert(format!("--{}", path.join("-")), value);
+        return Ok(());
+    }
+
+    for (key, child) in object {
+        // Properties like `$type` or `$description`.
+        if key.starts_with('$') {
+            continue;
+        }
+        path.push(key.clone());
+        collect(child, path, variables)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+fn export_value(value: &str) -> String {
+    match value
+        .strip_prefix("var(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .map(str::trim)
+        .filter(|referenced| referenced.starts_with("--") && !referenced.contains([',', ' ']))
+    {
+        Some(referenced) => match token_path(referenced) {
+            (Some(group), token) => format!("{{{group}.{token}}}"),
+            (None, token) => format!("{{{token}}}"),
+        },
+        None => value.to_owned(),
+    }
+}
+
+fn import_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(
+            match value
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+            {
+                Some(alias) => format!("var(--{})", alias.replace('.', "-")),
+                None => value.clone(),
+            },
+        ),
+        Value::Number(number) => Some(number.to_string()),
+        // A `fontFamily` token may list multiple families.
+        Value::Array(families) => families
+            .iter()
+            .map(|family| family.as_str().map(quote_font_family))
+            .collect::<Option<Vec<_>>>()
+            .map(|families| families.join(", ")),
+        _ => None,
+    }
+}
+
+fn quote_font_family(family: &str) -> String {
+    match family.contains(' ') && !family.starts_with('"') {
+        true => format!("\"{family}\""),
+        false => family.to_owned(),
+    }
+}
+
+fn infer_type(name: &str, value: &str) -> Option<&'static str> {
+    if name.ends_with("font-family") {
+        return Some("fontFamily");
+    }
+    if value.contains(char::is_whitespace) && !value.starts_with("rgb") && !value.starts_with("hsl")
+    {
+        return None;
+    }
+    if value.starts_with('#')
+        || value.starts_with("rgb(")
+        || value.starts_with("rgba(")
+        || value.starts_with("hsl(")
+        || value.starts_with("hsla(")
+        || matches!(value, "white" | "black" | "transparent")
+    {
+        return Some("color");
+    }
+    let number_end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    if number_end == 0 || value[..number_end].parse::<f64>().is_err() {
+        return None;
+    }
+    match &value[number_end..] {
+        "px" | "rem" | "em" => Some("dimension"),
+        "ms" | "s" => Some("duration"),
+        "" => Some("number"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_tokens, parse_tokens, tokens_to_scss};
+    use crate::{validate::variables, BaseTheme, ThemeBuilder};
+
+    #[test]
+    fn bundled_themes_round_trip() {
+        for theme in BaseTheme::ALL {
+            let imported = parse_tokens(&export_tokens(theme)).unwrap();
+            let original = variables(theme.source());
+            assert_eq!(imported.len(), original.len());
+            for (name, value) in original {
+                assert_eq!(imported[name], value, "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn exports_groups_types_and_aliases() {
+        let json = export_tokens(BaseTheme::Light);
+        let tokens = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(tokens["brand"]["color"]["$type"], "color");
+        assert_eq!(tokens["primary"]["color"]["$value"], "{brand.color}");
+        assert_eq!(tokens["grey"]["0"]["$value"], "#f8f8f8");
+    }
+
+    #[test]
+    fn imports_nested_groups_and_font_families() {
+        let json = r##"{
+            "primary": { "color": { "$type": "color", "$value": "#0b5394" } },
+            "button": {
+                "$description": "Buttons",
+                "border": { "radius": { "$value": "0" } }
+            },
+            "font": { "family": { "$value": ["Open Sans", "sans-serif"] } },
+            "alert": { "margin": { "$value": "{button.border.radius}" } }
+        }"##;
+        assert_eq!(
+            tokens_to_scss("corporate", json).unwrap(),
+            "[data-theme=\"corporate\"] {\n    --primary-color: #0b5394;\n    --button-border-radius: 0;\n    --font-family: \"Open Sans\", sans-serif;\n    --alert-margin: var(--button-border-radius);\n}\n"
+        );
+    }
+
+    #[test]
+    fn builder_applies_tokens() {
+        let theme = ThemeBuilder::new("corporate")
+            .tokens(r##"{ "grey": { "3": { "$value": "#123456" } } }"##)
+            .unwrap();
+        assert_eq!(theme.variables()["--grey-3"], "#123456");
+        assert!(theme.validate().is_ok());
+    }
+
+    #[test]
+    fn reports_invalid_tokens() {
+        let err = parse_tokens(r#"{ "grey": { "3": { "$value": true } } }"#).unwrap_err();
+        assert_eq!(err.token, "grey.3");
+    }
+}
@@ -0,0 +1,263 @@
+use std::path::Path;
+
+use crate::{builder::declared_variable, BaseTheme, GenerateError, ThemeBuilder};
+
+/// How `try_generate_with` reacts to problems found in custom themes.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Validation {
+    /// Do not validate custom themes.
+    Off,
+    /// Report problems as cargo build warnings.
+    #[default]
+    Warn,
+    /// Fail generation on any problem.
+    Error,
+}
+
+/// A variable which is most likely a typo of a variable defined by the bundled themes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Misspelling {
+    pub found: String,
+    pub expected: String,
+}
+
+/// The result of checking a theme against the variables defined by the bundled themes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Variables defined by the bundled themes but not by the checked theme.
+    pub missing: Vec<String>,
+    /// Variables defined by the checked theme, unknown to the bundled themes.
+    pub unknown: Vec<String>,
+    /// Unknown variables closely resembling a missing one.
+    pub misspelled: Vec<Misspelling>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.unknown.is_empty() && self.misspelled.is_empty()
+    }
+
+    /// Prints every problem as a `cargo:warning`. Only useful when called from a build script.
+    pub fn emit_cargo_warnings(&self, theme: &str) {
+        for line in self.to_string().lines() {
+            println!("cargo:warning=leptonic-theme: Theme '{theme}': {line}");
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for Misspelling { found, expected } in &self.misspelled {
+            writeln!(
+                f,
+                "Variable '{found}' is unknown. Did you mean '{expected}'?"
+            )?;
+        }
+        for missing in &self.missing {
+            writeln!(f, "Variable '{missing}' is missing.")?;
+        }
+        for unknown in &self.unknown {
+            writeln!(f, "Variable '{unknown}' is unknown.")?;
+        }
+        Ok(())
+    }
+}
+
+/// All variables a theme is expected to define, in declaration order.
+/// These are the variables defined by the bundled light theme.
+pub fn bundled_variables() -> Vec<&'static str> {
+    variables(BaseTheme::Light.source())
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Extracts all custom property declarations, as `(name, value)` pairs, from SCSS source code.
+pub fn variables(scss: &str) -> Vec<(&str, &str)> {
+    scss.lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            let name = declared_variable(line)?;
+            let value = line[name.len()..].trim_start().strip_prefix(':')?;
+            let value = value.split("//").next().unwrap_or(value);
+            let value = value.trim().trim_end_matches(';').trim_end();
+            Some((name, value))
+        })
+        .collect()
+}
+
+/// Checks the variables declared in `scss` against the variables defined by the bundled themes.
+pub fn validate_theme(scss: &str) -> ValidationReport {
+    let expected = bundled_variables();
+    let declared = variables(scss)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+
+    let mut missing = expected
+        .iter()
+        .filter(|name| !declared.contains(name))
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+
+    let mut report = ValidationReport::default();
+    let mut unknown = declared
+        .iter()
+        .filter(|name| !expected.contains(name))
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    unknown.sort();
+    unknown.dedup();
+
+    for found in unknown {
+        match closest(&found, &missing) {
+            Some(i) => report.misspelled.push(Misspelling {
+                found,
+                expected: missing.remove(i),
+            }),
+            None => report.unknown.push(found),
+        }
+    }
+    report.missing = missing;
+    report
+}
+
+/// Reads and validates a theme file.
+pub fn validate_theme_file(path: impl AsRef<Path>) -> Result<ValidationReport, GenerateError> {
+    let path = path.as_ref();
+    let scss = std::fs::read_to_string(path).map_err(GenerateError::io(path))?;
+    Ok(validate_theme(&scss))
+}
+
+impl ThemeBuilder {
+    /// Validates the variables set on this builder. As every other variable is taken from the base theme,
+    /// only misspelled variables can be reported. Other variables unknown to the bundled themes are
+    /// custom variables added on purpose.
+    pub fn validate(&self) -> ValidationReport {
+        let expected = bundled_variables();
+        let mut report = ValidationReport::default();
+        for found in self.variables().into_keys() {
+            if expected.contains(&found.as_str()) {
+                continue;
+            }
+            if let Some(i) = closest(&found, &expected) {
+                report.misspelled.push(Misspelling {
+                    found,
+                    expected: expected[i].to_owned(),
+                });
+            }
+        }
+        report
+    }
+}
+
+/// Index of the candidate `found` most likely is a typo of.
+fn closest(found: &str, candidates: &[impl AsRef<str>]) -> Option<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .map(|(i, candidate)| (i, edit_distance(found, candidate.as_ref())))
+        .min_by_key(|(_, distance)| *distance)
+        .filter(|(_, distance)| *distance <= max_typo_distance(found))
+        .map(|(i, _)| i)
+}
+
+fn max_typo_distance(name: &str) -> usize {
+    match name.len() {
+        0..=8 => 1,
+        9..=20 => 2,
+        _ => 3,
+    }
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, validate_theme, variables, Misspelling};
+    use crate::{BaseTheme, ThemeBuilder};
+
+    #[test]
+    fn bundled_themes_are_complete() {
+        for theme in BaseTheme::ALL {
+            let report = validate_theme(theme.source());
+            assert!(report.is_ok(), "{}: {report}", theme.name());
+        }
+    }
+
+    #[test]
+    fn parses_values_without_comments() {
+        let parsed =
+            variables("[data-theme=\"x\"] {\n    --a: #fff; // white\n    --b: var(--a);\n}");
+        assert_eq!(parsed, vec![("--a", "#fff"), ("--b", "var(--a)")]);
+    }
+
+    #[test]
+    fn reports_missing_unknown_and_misspelled_variables() {
+        let scss = BaseTheme::Light
+            .source()
+            .replace("--brand-color:", "--brand-colour:")
+            .replace("--alert-padding:", "--not-a-theme-variable:");
+        let report = validate_theme(&scss);
+        assert_eq!(
+            report.misspelled,
+            vec![Misspelling {
+                found: "--brand-colour".to_owned(),
+                expected: "--brand-color".to_owned(),
+            }]
+        );
+        assert_eq!(report.missing, vec!["--alert-padding".to_owned()]);
+        assert_eq!(report.unknown, vec!["--not-a-theme-variable".to_owned()]);
+    }
+
+    #[test]
+    fn reports_repeated_unknown_variables_once() {
+        let scss = format!(
+            "{}\n--unknown-a: 1;\n--unknown-b: 1;\n--unknown-a: 2;",
+            BaseTheme::Light.source()
+        );
+        assert_eq!(
+            validate_theme(&scss).unknown,
+            vec!["--unknown-a".to_owned(), "--unknown-b".to_owned()]
+        );
+    }
+
+    #[test]
+    fn builder_reports_misspelled_but_not_custom_variables() {
+        let report = ThemeBuilder::new("custom")
+            .brand_color("#0b5394")
+            .variable("--button-border-radiu", "0")
+            .variable("--my-own-variable", "1px")
+            .validate();
+        assert_eq!(
+            report.misspelled,
+            vec![Misspelling {
+                found: "--button-border-radiu".to_owned(),
+                expected: "--button-border-radius".to_owned(),
+            }]
+        );
+        assert!(report.unknown.is_empty());
+        assert!(report.missing.is_empty());
+    }
+
+    #[test]
+    fn computes_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+}
@@ -1,24 +1,29 @@
 use crate::{
-    contexts::global_mouseup_event::GlobalMouseupEvent, math::project_into_range, prelude::*,
-    RelativeMousePosition, TrackedElementClientBoundingRect,
+    color_swatches::ColorSwatchesProps, contexts::global_mouseup_event::GlobalMouseupEvent,
+    math::project_into_range, prelude::*, RelativeMousePosition, TrackedElementClientBoundingRect,
 };
 use indoc::formatdoc;
 use leptos::*;
 
+/// Renders the given color. A checkerboard pattern shows through when an `alpha` below 1.0 is given.
 #[component]
 pub fn ColorPreview(
     #[prop(into)] rgb: Signal<RGB8>,
+    /// Opacity of the color, in range 0..=1.
+    #[prop(into, optional)]
+    alpha: Option<Signal<f64>>,
     #[prop(into, optional)] id: Option<AttributeValue>,
     #[prop(into, optional)] class: Option<AttributeValue>,
     #[prop(into, optional)] style: Option<AttributeValue>,
 ) -> impl IntoView {
-    let background_color = move || {
+    let color = move || {
         let RGB8 { r, g, b } = rgb.get();
-        format!("rgb({r}, {g}, {b})")
+        let alpha = alpha.map(|alpha| alpha.get()).unwrap_or(1.0);
+        format!("rgba({r}, {g}, {b}, {alpha})")
     };
 
     view! {
-        <leptonic-color-preview id=id class=class style=style style:background-color=background_color>
+        <leptonic-color-preview id=id class=class style=style style=("--color-preview-color", color)>
         </leptonic-color-preview>
     }
 }
@@ -163,14 +168,65 @@ pub fn HueSlider(#[prop(into)] hue: Signal<f64>, #[prop(into)] set_hue: Out<f64>
     }
 }
 
+/// A slider picking the opacity of `rgb`, rendered in front of a checkerboard pattern.
+#[component]
+pub fn AlphaSlider(
+    #[prop(into)] alpha: Signal<f64>,
+    #[prop(into)] set_alpha: Out<f64>,
+    #[prop(into)] rgb: Signal<RGB8>,
+) -> impl IntoView {
+    let style = move || {
+        let RGB8 { r, g, b } = rgb.get();
+        let alpha = alpha.get();
+        format!(
+            "--alpha-slider-color: rgb({r}, {g}, {b}); --slider-knob-background-color: rgba({r}, {g}, {b}, {alpha}); --slider-knob-halo-background-color: rgb({r}, {g}, {b});"
+        )
+    };
+    view! {
+        <leptonic-alpha-slider>
+            <Slider min=0.0 max=1.0
+                value=alpha set_value=set_alpha
+                marks=SliderMarks::None
+                popover=SliderPopover::Never
+                class="alpha-slider"
+                style=style
+            />
+        </leptonic-alpha-slider>
+    }
+}
+
+/// Picks a color in the HSV color space.
+/// Either `hsv` and `set_hsv` or `hsva` and `set_hsva` must be given. Using the latter, the opacity can be picked as well.
+/// Given `swatches` or `recent` colors, these are shown below the picker and can be clicked to pick them.
 #[component]
 pub fn ColorPicker(
-    #[prop(into)] hsv: Signal<HSV>,
-    #[prop(into)] set_hsv: Out<HSV>,
+    /// An opaque color.
+    #[prop(into, optional)]
+    hsv: Option<Signal<HSV>>,
+    #[prop(into, optional)] set_hsv: Option<Out<HSV>>,
+    /// A color with an opacity in range 0..=1.
+    #[prop(into, optional)]
+    hsva: Option<Signal<Alpha<HSV>>>,
+    #[prop(into, optional)] set_hsva: Option<Out<Alpha<HSV>>>,
+    #[prop(into, optional)] swatches: Option<MaybeSignal<Vec<Swatch>>>,
+    /// Remembers the color whenever the user finished picking it.
+    #[prop(optional)]
+    recent: Option<RecentColors>,
 ) -> impl IntoView {
+    let (hsv, set_hsv, alpha, set_alpha) = match (hsv.zip(set_hsv), hsva.zip(set_hsva)) {
+        (Some((hsv, set_hsv)), None) => (hsv, set_hsv, None, None),
+        (None, Some((hsva, set_hsva))) => (
+            Signal::derive(move || hsva.get().color),
+            Out::from(move |hsv: HSV| set_hsva.set(Alpha::new(hsv, hsva.get_untracked().alpha))),
+            Some(Signal::derive(move || hsva.get().alpha)),
+            Some(Out::from(move |alpha: f64| {
+                set_hsva.set(Alpha::new(hsva.get_untracked().color, alpha))
+            })),
+        ),
+        _ => panic!("A ColorPicker requires either `hsv` and `set_hsv` or `hsva` and `set_hsva`."),
+    };
+
     let hue = Signal::derive(move || hsv.get().hue);
-    let saturation = Signal::derive(move || hsv.get().saturation);
-    let value = Signal::derive(move || hsv.get().value);
 
     let set_hsv_c1 = set_hsv.clone();
     let set_hsv_c2 = set_hsv.clone();
@@ -186,66 +242,380 @@ pub fn ColorPicker(
         Callback::new(move |new_value| set_hsv.set(hsv.get_untracked().with_value(new_value)));
 
     let rgb = Signal::derive(move || RGB8::from(hsv.get()));
+    let opacity = Signal::derive(move || alpha.map(|alpha| alpha.get()).unwrap_or(1.0));
 
-    view! {
-        <leptonic-color-picker>
-            <div style="display: flex; flex-direction: row; justify-content: center; align-items: center; height: 20em;">
-                <ColorPreview rgb=rgb style="width: 20%; height: 100%;"/>
-                <ColorPalette hsv=hsv
-                    set_saturation=set_saturation.clone()
-                    set_value=set_value.clone()
-                    style="width: 80%; height: 100%;"
-                />
-            </div>
+    let remember = move || {
+        if let Some(recent) = recent {
+            recent.remember(RGBA8::from(Alpha::new(
+                rgb.get_untracked(),
+                opacity.get_untracked(),
+            )));
+        }
+    };
 
-            <HueSlider hue=hue set_hue=set_hue.clone()/>
+    // Picking ends when the mouse is released, which may happen outside of the picker while dragging.
+    let (picking, set_picking) = create_signal(false);
+    let GlobalMouseupEvent {
+        read_signal: mouse_up,
+        ..
+    } = expect_context();
+    create_effect(move |_| {
+        if mouse_up.get().is_some() && picking.get_untracked() {
+            set_picking.set(false);
+            remember();
+        }
+    });
 
-            <div style="display: flex; flex-direction: row;">
-                <Field style="width: 32%; margin-right: 2%;">
-                    <FieldLabel>"Hue"</FieldLabel>
-                    <NumberInput min=0.0 max=360.0 step=1.0
-                        get=hue
-                        set=set_hue
-                    />
-                </Field>
-                <Field style="width: 32%; margin-right: 2%;">
-                    <FieldLabel>"Saturation"</FieldLabel>
-                    <NumberInput min=0.0 max=1.0 step=0.01
-                        get=saturation
-                        set=set_saturation
-                    />
-                </Field>
-                <Field style="width: 32%; margin-right: 0%;">
-                    <FieldLabel>"Value"</FieldLabel>
-                    <NumberInput min=0.0 max=1.0 step=0.01
-                        get=value
-                        set=set_value
-                    />
-                </Field>
-            </div>
+    let swatches = match (swatches, recent) {
+        (None, None) => None,
+        (swatches, recent) => Some(ColorSwatches(ColorSwatchesProps {
+            swatches: swatches.unwrap_or_default(),
+            set_color: Out::from(move |color: RGBA8| {
+                let picked = with_rgb8(hsv.get_untracked(), color.rgb8());
+                match set_hsva {
+                    Some(set_hsva) => set_hsva.set(Alpha::new(picked, color.alpha())),
+                    None => set_hsv.set(picked),
+                }
+            }),
+            recent,
+            id: None,
+            class: None,
+            style: None,
+        })),
+    };
 
-            <div style="display: flex; flex-direction: row;">
-                <Field style="width: 32%; margin-right: 2%;">
-                    <FieldLabel>"R"</FieldLabel>
-                    <NumberInput min=0.0 max=255.0 step=1.0
-                        get=Signal::derive(move || rgb.get().r as f64)
-                    />
-                </Field>
-                <Field style="width: 32%; margin-right: 2%;">
-                    <FieldLabel>"G"</FieldLabel>
-                    <NumberInput min=0.0 max=255.0 step=1.0
-                        get=Signal::derive(move || rgb.get().g as f64)
-                    />
-                </Field>
-                <Field style="width: 32%; margin-right: 0%;">
-                    <FieldLabel>"B"</FieldLabel>
-                    <NumberInput min=0.0 max=255.0 step=1.0
-                        get=Signal::derive(move || rgb.get().b as f64)
+    let alpha_slider = alpha.zip(set_alpha).map(|(alpha, set_alpha)| {
+        view! { <AlphaSlider alpha=alpha set_alpha=set_alpha rgb=rgb/> }
+    });
+    let alpha_input = alpha.zip(set_alpha).map(|(alpha, set_alpha)| {
+        view! {
+            <Field>
+                <FieldLabel>"Alpha"</FieldLabel>
+                <NumberInput min=0.0 max=1.0 step=0.01
+                    get=alpha
+                    set=set_alpha
+                />
+            </Field>
+        }
+    });
+
+    view! {
+        // Text inputs fire a change event when their value is committed.
+        <leptonic-color-picker on:change=move |_| remember()>
+            <div
+                on:mousedown=move |_| set_picking.set(true)
+                on:touchend=move |_| remember()
+            >
+                <div style="display: flex; flex-direction: row; justify-content: center; align-items: center; height: 20em;">
+                    <ColorPreview rgb=rgb alpha=opacity style="width: 20%; height: 100%;"/>
+                    <ColorPalette hsv=hsv
+                        set_saturation=set_saturation
+                        set_value=set_value
+                        style="width: 80%; height: 100%;"
                     />
-                </Field>
+                </div>
+
+                <HueSlider hue=hue set_hue=set_hue/>
+
+                {alpha_slider}
             </div>
 
-            <P>"Hex: #"{move || format!("{:X}", rgb.get())}</P>
+            {alpha_input}
+
+            <ColorInputs hsv=hsv set_hsv=set_hsv/>
+
+            {swatches}
         </leptonic-color-picker>
     }
 }
+
+/// The notation used by `<ColorInputs>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorFormat {
+    #[default]
+    Hex,
+    Rgb,
+    Hsv,
+    Hsl,
+}
+
+impl ColorFormat {
+    pub const ALL: [ColorFormat; 4] = [
+        ColorFormat::Hex,
+        ColorFormat::Rgb,
+        ColorFormat::Hsv,
+        ColorFormat::Hsl,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorFormat::Hex => "HEX",
+            ColorFormat::Rgb => "RGB",
+            ColorFormat::Hsv => "HSV",
+            ColorFormat::Hsl => "HSL",
+        }
+    }
+
+    /// Labels of the individually editable components. A hex color is edited as a whole.
+    fn components(self) -> &'static [&'static str] {
+        match self {
+            ColorFormat::Hex => &["Hex"],
+            ColorFormat::Rgb => &["R", "G", "B"],
+            ColorFormat::Hsv => &["H", "S", "V"],
+            ColorFormat::Hsl => &["H", "S", "L"],
+        }
+    }
+
+    /// The text of component `index` of `hsv`.
+    /// Hues are given in degrees, all other components of HSV and HSL as percentages.
+    fn format(self, index: usize, hsv: HSV) -> String {
+        match self {
+            ColorFormat::Hex => RGB8::from(hsv).to_string(),
+            ColorFormat::Rgb => {
+                let RGB8 { r, g, b } = RGB8::from(hsv);
+                [r, g, b][index].to_string()
+            }
+            ColorFormat::Hsv => format_component(index, [hsv.hue, hsv.saturation, hsv.value]),
+            ColorFormat::Hsl => {
+                let hsl = HSL::from(hsv);
+                format_component(index, [hsl.hue, hsl.saturation, hsl.lightness])
+            }
+        }
+    }
+
+    /// The color resulting from replacing component `index` of `hsv` with `text`, or `None` if `text` is not valid.
+    /// All other components are taken from the unrounded `hsv`, so that editing one of them never alters the others.
+    fn parse(self, index: usize, text: &str, hsv: HSV) -> Option<HSV> {
+        match self {
+            ColorFormat::Hex => {
+                let hex = text.trim().trim_start_matches('#');
+                if !matches!(hex.len(), 3 | 6) {
+                    return None;
+                }
+                let rgb = format!("#{hex}").parse::<RGB8>().ok()?;
+                Some(with_rgb8(hsv, rgb))
+            }
+            ColorFormat::Rgb => {
+                let channel = text.trim().parse::<u8>().ok()?;
+                let mut rgb = RGB8::from(hsv);
+                *[&mut rgb.r, &mut rgb.g, &mut rgb.b][index] = channel;
+                Some(with_rgb8(hsv, rgb))
+            }
+            ColorFormat::Hsv => {
+                let number = parse_component(index, text)?;
+                Some(match index {
+                    0 => hsv.with_hue(number),
+                    1 => hsv.with_saturation(number),
+                    _ => hsv.with_value(number),
+                })
+            }
+            ColorFormat::Hsl => {
+                let number = parse_component(index, text)?;
+                let mut hsl = HSL::from(hsv);
+                *[&mut hsl.hue, &mut hsl.saturation, &mut hsl.lightness][index] = number;
+                Some(keep_hue(hsv, HSV::from(hsl)))
+            }
+        }
+    }
+}
+
+/// Formats the hue (index 0) in degrees and the other components as percentages, with at most one decimal place.
+fn format_component(index: usize, components: [f64; 3]) -> String {
+    let value = match index {
+        0 => components[0].rem_euclid(360.0),
+        _ => components[index] * 100.0,
+    };
+    // Adding 0.0 turns a negative zero into a positive one.
+    ((value * 10.0).round() / 10.0 + 0.0).to_string()
+}
+
+/// Parses a hue in degrees (index 0) or a percentage, both optionally followed by their unit.
+fn parse_component(index: usize, text: &str) -> Option<f64> {
+    let (unit, max) = match index {
+        0 => ('°', 360.0),
+        _ => ('%', 100.0),
+    };
+    let number = text
+        .trim()
+        .trim_end_matches(unit)
+        .trim_end()
+        .parse::<f64>()
+        .ok()?;
+    match (0.0..=max).contains(&number) {
+        true if index == 0 => Some(number),
+        true => Some(number / 100.0),
+        false => None,
+    }
+}
+
+/// `hsv` is returned unchanged when it already represents `rgb`,
+/// so that re-entering the displayed color does not round the picked one.
+fn with_rgb8(hsv: HSV, rgb: RGB8) -> HSV {
+    match RGB8::from(hsv) == rgb {
+        true => hsv,
+        false => keep_hue(hsv, HSV::from(rgb)),
+    }
+}
+
+/// Greys have no hue and black has no saturation either.
+/// Keeps them from `previous`, so that the palette and hue slider do not jump while typing a color.
+fn keep_hue(previous: HSV, next: HSV) -> HSV {
+    HSV {
+        hue: match next.saturation == 0.0 || next.value == 0.0 {
+            true => previous.hue,
+            false => next.hue,
+        },
+        saturation: match next.value == 0.0 {
+            true => previous.saturation,
+            false => next.saturation,
+        },
+        value: next.value,
+    }
+}
+
+/// Text inputs editing `hsv` in a selectable format.
+#[component]
+pub fn ColorInputs(
+    #[prop(into)] hsv: Signal<HSV>,
+    #[prop(into)] set_hsv: Out<HSV>,
+    /// The format shown initially.
+    #[prop(optional)]
+    format: ColorFormat,
+) -> impl IntoView {
+    let (format, set_format) = create_signal(format);
+
+    view! {
+        <leptonic-color-inputs>
+            <Select
+                options=ColorFormat::ALL.to_vec()
+                selected=format
+                set_selected=set_format
+                search_text_provider=move |format: ColorFormat| format.label().to_owned()
+                render_option=move |format: ColorFormat| format.label()
+                class="color-format"
+            />
+            {move || {
+                let format = format.get();
+                format
+                    .components()
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .map(|(index, label)| view! {
+                        <ColorComponentInput format=format index=index label=label hsv=hsv set_hsv=set_hsv/>
+                    })
+                    .collect_view()
+            }}
+        </leptonic-color-inputs>
+    }
+}
+
+#[component]
+fn ColorComponentInput(
+    format: ColorFormat,
+    index: usize,
+    label: &'static str,
+    hsv: Signal<HSV>,
+    set_hsv: Out<HSV>,
+) -> impl IntoView {
+    let text = create_rw_signal(format.format(index, hsv.get_untracked()));
+
+    // The color last set from this input. Its text is not re-formatted when this color comes back in,
+    // as that would interfere with typing, e.g. by turning "12." into "12".
+    let emitted = store_value(None::<HSV>);
+    create_effect(move |_| {
+        let hsv = hsv.get();
+        if emitted.get_value() != Some(hsv) {
+            text.set(format.format(index, hsv));
+        }
+    });
+
+    let invalid = move || match format.parse(index, &text.get(), hsv.get_untracked()) {
+        Some(_) => "",
+        None => "invalid",
+    };
+
+    let set_text = move |new_text: String| {
+        let current = hsv.get_untracked();
+        if let Some(new_hsv) = format.parse(index, &new_text, current) {
+            if new_hsv != current {
+                emitted.set_value(Some(new_hsv));
+                set_hsv.set(new_hsv);
+            }
+        }
+        text.set(new_text);
+    };
+
+    view! {
+        <Field>
+            <FieldLabel>{label}</FieldLabel>
+            <TextInput get=text set=set_text class=invalid/>
+        </Field>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorFormat;
+    use crate::prelude::{HSV, RGB8};
+
+    fn hsv(hue: f64, saturation: f64, value: f64) -> HSV {
+        HSV {
+            hue,
+            saturation,
+            value,
+        }
+    }
+
+    #[test]
+    fn formats_components() {
+        let color = hsv(210.04, 0.3333, 0.5);
+        assert_eq!(ColorFormat::Hex.format(0, color), "#556A80");
+        assert_eq!(ColorFormat::Rgb.format(1, color), "106");
+        assert_eq!(ColorFormat::Hsv.format(0, color), "210");
+        assert_eq!(ColorFormat::Hsv.format(1, color), "33.3");
+        assert_eq!(ColorFormat::Hsl.format(2, color), "41.7");
+    }
+
+    #[test]
+    fn parses_components_and_rejects_invalid_text() {
+        let color = hsv(0.0, 1.0, 1.0);
+        let rgb = |color: Option<HSV>| color.map(RGB8::from);
+        assert_eq!(
+            rgb(ColorFormat::Hex.parse(0, "0a141e", color)),
+            Some(RGB8::from((10, 20, 30)))
+        );
+        assert_eq!(
+            rgb(ColorFormat::Rgb.parse(2, " 255 ", color)),
+            Some(RGB8::from((255, 0, 255)))
+        );
+        assert_eq!(
+            ColorFormat::Hsv.parse(1, "50%", color),
+            Some(hsv(0.0, 0.5, 1.0))
+        );
+        assert_eq!(ColorFormat::Hex.parse(0, "#0a14", color), None);
+        assert_eq!(ColorFormat::Rgb.parse(0, "256", color), None);
+        assert_eq!(ColorFormat::Hsv.parse(0, "361", color), None);
+    }
+
+    #[test]
+    fn editing_does_not_drift() {
+        let color = hsv(123.456, 0.789, 0.654);
+        // Re-entering the displayed (rounded) color keeps the exact one.
+        let hex = ColorFormat::Hex.format(0, color);
+        assert_eq!(ColorFormat::Hex.parse(0, &hex, color), Some(color));
+        let green = ColorFormat::Rgb.format(1, color);
+        assert_eq!(ColorFormat::Rgb.parse(1, &green, color), Some(color));
+        // Editing one component leaves the others untouched.
+        let edited = ColorFormat::Hsv.parse(2, "50", color).unwrap();
+        assert_eq!(
+            (edited.hue, edited.saturation),
+            (color.hue, color.saturation)
+        );
+        // Greys and black keep the previous hue and saturation.
+        let grey = ColorFormat::Hex.parse(0, "#808080", color).unwrap();
+        assert_eq!(grey.hue, color.hue);
+        let black = ColorFormat::Hsl.parse(2, "0", color).unwrap();
+        assert_eq!((black.hue, black.saturation), (color.hue, color.saturation));
+    }
+}
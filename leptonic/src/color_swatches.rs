@@ -0,0 +1,193 @@
+use leptos::*;
+use web_sys::Element;
+
+use crate::{create_signal_ls, prelude::*};
+
+/// Number of colors remembered by `RecentColors`.
+pub const MAX_RECENT_COLORS: usize = 8;
+
+/// A preset color rendered by `<ColorSwatches>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Swatch {
+    Color(RGBA8),
+    /// A color variable of the active theme, e.g. `--brand-color`. It is resolved when clicked,
+    /// so the swatch always shows and picks the color of the theme currently active.
+    Variable(String),
+}
+
+impl Swatch {
+    /// Swatches for the brand and semantic colors defined by all bundled themes.
+    pub fn theme() -> Vec<Swatch> {
+        [
+            "--brand-color",
+            "--secondary-color",
+            "--info-color",
+            "--success-color",
+            "--warn-color",
+            "--danger-color",
+        ]
+        .into_iter()
+        .map(Swatch::from)
+        .collect()
+    }
+
+    /// CSS value used as the background of the swatch.
+    fn css(&self) -> String {
+        match self {
+            Swatch::Color(RGBA8 { r, g, b, a }) => {
+                format!("rgba({r}, {g}, {b}, {})", *a as f64 / 255.0)
+            }
+            Swatch::Variable(name) => format!("var({name})"),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Swatch::Color(color) => color.to_string(),
+            Swatch::Variable(name) => name.clone(),
+        }
+    }
+
+    /// The color of this swatch, reading variables from the computed style of `element`.
+    fn resolve(&self, element: &Element) -> Option<RGBA8> {
+        match self {
+            Swatch::Color(color) => Some(*color),
+            Swatch::Variable(name) => {
+                let value = window()
+                    .get_computed_style(element)
+                    .ok()??
+                    .get_property_value(name)
+                    .ok()?;
+                match value.parse::<RGBA8>() {
+                    Ok(color) => Some(color),
+                    Err(err) => {
+                        tracing::warn!(%err, "Could not use the value of '{name}' as a color.");
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<RGBA8> for Swatch {
+    fn from(color: RGBA8) -> Self {
+        Swatch::Color(color)
+    }
+}
+
+impl From<RGB8> for Swatch {
+    fn from(color: RGB8) -> Self {
+        Swatch::Color(color.into())
+    }
+}
+
+impl From<&str> for Swatch {
+    fn from(name: &str) -> Self {
+        Swatch::Variable(name.to_owned())
+    }
+}
+
+/// Colors recently picked by the user, most recent first, persisted in local storage.
+#[derive(Debug, Clone, Copy)]
+pub struct RecentColors {
+    pub colors: ReadSignal<Vec<RGBA8>>,
+    set_colors: WriteSignal<Vec<RGBA8>>,
+}
+
+impl RecentColors {
+    /// Loads the colors stored under `key`. Components sharing recent colors must share this value,
+    /// as multiple instances using the same key would not see each others changes.
+    pub fn new(key: &'static str) -> Self {
+        let (colors, set_colors) = create_signal_ls(key, Vec::new());
+        Self { colors, set_colors }
+    }
+
+    /// Moves `color` to the front, forgetting the oldest color when more than `MAX_RECENT_COLORS` are known.
+    pub fn remember(&self, color: RGBA8) {
+        self.set_colors
+            .update(|colors| remember(colors, color, MAX_RECENT_COLORS));
+    }
+}
+
+fn remember(colors: &mut Vec<RGBA8>, color: RGBA8, max: usize) {
+    colors.retain(|known| *known != color);
+    colors.insert(0, color);
+    colors.truncate(max);
+}
+
+/// Renders clickable preset colors and, given `recent`, a row of recently picked colors.
+/// Clicking a swatch sets the color and remembers it as recently picked.
+#[component]
+pub fn ColorSwatches(
+    #[prop(into)] swatches: MaybeSignal<Vec<Swatch>>,
+    #[prop(into)] set_color: Out<RGBA8>,
+    #[prop(optional)] recent: Option<RecentColors>,
+    #[prop(into, optional)] id: Option<AttributeValue>,
+    #[prop(into, optional)] class: Option<AttributeValue>,
+    #[prop(into, optional)] style: Option<AttributeValue>,
+) -> impl IntoView {
+    let pick = move |swatch: &Swatch, element: Element| {
+        if let Some(color) = swatch.resolve(&element) {
+            set_color.set(color);
+            if let Some(recent) = recent {
+                recent.remember(color);
+            }
+        }
+    };
+
+    let render = move |swatches: Vec<Swatch>| {
+        swatches
+            .into_iter()
+            .map(|swatch| {
+                let css = swatch.css();
+                let label = swatch.label();
+                view! {
+                    <button
+                        class="swatch"
+                        type="button"
+                        title=label.clone()
+                        aria-label=label
+                        style=("--swatch-color", css)
+                        on:click=move |e| pick(&swatch, event_target::<Element>(&e))
+                    />
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <leptonic-color-swatches id=id class=class style=style>
+            <div class="swatches">
+                { move || render(swatches.get()) }
+            </div>
+            { move || recent
+                .map(|recent| recent.colors.get())
+                .filter(|colors| !colors.is_empty())
+                .map(|colors| view! {
+                    <div class="swatches recent">
+                        { render(colors.into_iter().map(Swatch::from).collect()) }
+                    </div>
+                })
+            }
+        </leptonic-color-swatches>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remember;
+    use crate::prelude::RGBA8;
+
+    #[test]
+    fn remembers_unique_colors_most_recent_first() {
+        let color = |r: u8| RGBA8::from((r, 0, 0, 255));
+        let mut colors = Vec::new();
+        for r in [1, 2, 3, 2] {
+            remember(&mut colors, color(r), 3);
+        }
+        assert_eq!(colors, vec![color(2), color(3), color(1)]);
+        remember(&mut colors, color(4), 3);
+        assert_eq!(colors, vec![color(4), color(2), color(3)]);
+    }
+}
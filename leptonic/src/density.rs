@@ -0,0 +1,52 @@
+use leptos::*;
+
+/// How tightly components are laid out.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Density {
+    /// Reduced paddings, heights and font sizes, e.g. for data heavy admin screens.
+    Compact,
+    #[default]
+    Comfortable,
+}
+
+impl Density {
+    /// The value of the `data-density` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Density::Compact => "compact",
+            Density::Comfortable => "comfortable",
+        }
+    }
+}
+
+/// Provided by every <DensityProvider>, including the one rendered by <Root>.
+#[derive(Debug, Clone, Copy)]
+pub struct DensityContext {
+    pub density: Signal<Density>,
+}
+
+/// The density of the closest <DensityProvider>, falling back to `Density::Comfortable` when rendered outside of one.
+pub fn use_density() -> Signal<Density> {
+    use_context::<DensityContext>()
+        .map(|context| context.density)
+        .unwrap_or_else(|| Signal::derive(Density::default))
+}
+
+/// Sets the density of all buttons, inputs, selects, tables, tabs and chips in this subtree.
+/// Providers can be nested, the closest one wins.
+///
+/// The scale of each density is controlled by the `--density-compact-scale` and `--density-comfortable-scale` theme variables.
+#[component]
+pub fn DensityProvider(
+    #[prop(into)] density: MaybeSignal<Density>,
+    children: Children,
+) -> impl IntoView {
+    let density = Signal::derive(move || density.get());
+    provide_context(DensityContext { density });
+
+    view! {
+        <leptonic-density-provider data-density=move || density.get().as_str()>
+            { children() }
+        </leptonic-density-provider>
+    }
+}
@@ -0,0 +1,59 @@
+use leptos::*;
+
+/// The inline (reading) direction of the document.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Left-to-right, e.g. English.
+    #[default]
+    Ltr,
+    /// Right-to-left, e.g. Arabic or Hebrew.
+    Rtl,
+}
+
+impl Direction {
+    /// The value of the `dir` HTML attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+
+    pub fn is_rtl(self) -> bool {
+        self == Direction::Rtl
+    }
+
+    /// Converts a relative position measured from the physical left edge (0..1) into one measured from the inline start edge.
+    /// Applying this twice yields the original position.
+    pub fn mirror(self, relative: f64) -> f64 {
+        match self {
+            Direction::Ltr => relative,
+            Direction::Rtl => 1.0 - relative,
+        }
+    }
+}
+
+/// Provided by <Root>.
+#[derive(Debug, Clone, Copy)]
+pub struct DirectionContext {
+    pub direction: Signal<Direction>,
+}
+
+/// The direction provided by <Root>, falling back to `Direction::Ltr` when rendered outside of it.
+pub fn use_direction() -> Signal<Direction> {
+    use_context::<DirectionContext>()
+        .map(|context| context.direction)
+        .unwrap_or_else(|| Signal::derive(Direction::default))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Direction;
+
+    #[test]
+    fn mirrors_only_right_to_left() {
+        assert_eq!(Direction::Ltr.mirror(0.25), 0.25);
+        assert_eq!(Direction::Rtl.mirror(0.25), 0.75);
+        assert_eq!(Direction::Rtl.mirror(0.0), 1.0);
+    }
+}
@@ -1,11 +1,17 @@
 use leptos::*;
 use leptos_use::{use_interval_fn_with_options, utils::Pausable, UseIntervalFnOptions};
 
+use crate::direction::{use_direction, Direction};
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DrawerSide {
     #[default]
     Left,
     Right,
+    /// Left in left-to-right layouts, right in right-to-left layouts.
+    Start,
+    /// Right in left-to-right layouts, left in right-to-left layouts.
+    End,
 }
 
 impl DrawerSide {
@@ -13,6 +19,21 @@ impl DrawerSide {
         match self {
             DrawerSide::Left => "left",
             DrawerSide::Right => "right",
+            DrawerSide::Start => "start",
+            DrawerSide::End => "end",
+        }
+    }
+
+    /// The physical side (`Left` or `Right`) this side refers to in the given direction.
+    pub fn resolve(self, direction: Direction) -> Self {
+        match (self, direction) {
+            (DrawerSide::Start, Direction::Ltr) | (DrawerSide::End, Direction::Rtl) => {
+                DrawerSide::Left
+            }
+            (DrawerSide::Start, Direction::Rtl) | (DrawerSide::End, Direction::Ltr) => {
+                DrawerSide::Right
+            }
+            (side, _) => side,
         }
     }
 }
@@ -35,6 +56,7 @@ pub fn Drawer(
     children: Children,
 ) -> impl IntoView {
     let memoized_shown = create_memo(move |_| shown.get());
+    let direction = use_direction();
 
     let (anim_state, set_anim_state) = create_signal(match memoized_shown.get_untracked() {
         true => DrawerAnimationState::Shown,
@@ -105,7 +127,7 @@ pub fn Drawer(
             class:hiding=move || anim_state.get() == DrawerAnimationState::Hiding
             class:hidden=move || anim_state.get() == DrawerAnimationState::Hidden
             style=style
-            data-side=side.to_str()
+            data-side=move || side.resolve(direction.get()).to_str()
         >
             { children() }
         </leptonic-drawer>
@@ -0,0 +1,478 @@
+use std::fmt::{Display, Formatter};
+
+use leptos::*;
+use web_sys::KeyboardEvent;
+
+use crate::{
+    color::Interpolate, contexts::global_mouseup_event::GlobalMouseupEvent,
+    direction::use_direction, prelude::*, slider::value_after_key, RelativeMousePosition,
+    TrackedElementClientBoundingRect,
+};
+
+/// `Gradient::remove_stop` keeps at least this many stops, as CSS gradients require two of them.
+pub const MIN_GRADIENT_STOPS: usize = 2;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GradientKind {
+    #[default]
+    Linear,
+    /// A circle around the center of the element.
+    Radial,
+}
+
+impl GradientKind {
+    pub const ALL: [GradientKind; 2] = [GradientKind::Linear, GradientKind::Radial];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GradientKind::Linear => "Linear",
+            GradientKind::Radial => "Radial",
+        }
+    }
+}
+
+/// A color at a `position` along the gradient line, in range 0..=1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    pub color: Alpha<HSV>,
+    pub position: f64,
+}
+
+impl ColorStop {
+    pub fn new(color: impl Into<Alpha<HSV>>, position: f64) -> Self {
+        Self {
+            color: color.into(),
+            position,
+        }
+    }
+}
+
+/// A CSS gradient. Its `Display` implementation renders the CSS value, e.g. `linear-gradient(90deg, #FF0000 0%, #0000FF 100%)`.
+/// Stops are kept in the order in which they were added and are sorted by position when rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    /// Direction of linear gradients in degrees, 0 pointing up and 90 pointing right. Ignored by radial gradients.
+    pub angle: f64,
+    pub stops: Vec<ColorStop>,
+}
+
+impl Default for Gradient {
+    /// A linear gradient from red to blue, running from left to right.
+    fn default() -> Self {
+        Self::linear(
+            90.0,
+            vec![
+                ColorStop::new(Alpha::opaque(HSV::new()), 0.0),
+                ColorStop::new(Alpha::opaque(HSV::from_hue_fully_saturated(240.0)), 1.0),
+            ],
+        )
+    }
+}
+
+impl Gradient {
+    pub fn linear(angle: f64, stops: Vec<ColorStop>) -> Self {
+        Self {
+            kind: GradientKind::Linear,
+            angle,
+            stops,
+        }
+    }
+
+    pub fn radial(stops: Vec<ColorStop>) -> Self {
+        Self {
+            kind: GradientKind::Radial,
+            angle: 0.0,
+            stops,
+        }
+    }
+
+    /// The stops ordered by position. Stops sharing a position keep their order, creating a hard edge.
+    pub fn sorted_stops(&self) -> Vec<ColorStop> {
+        let mut stops = self.stops.clone();
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        stops
+    }
+
+    /// The color rendered at `position`, interpolated in sRGB like browsers do. Transparent if there are no stops.
+    pub fn color_at(&self, position: f64) -> Alpha<HSV> {
+        let stops = self.sorted_stops();
+        match stops.iter().position(|stop| stop.position > position) {
+            Some(0) => stops[0].color,
+            Some(next) => {
+                let (from, to) = (stops[next - 1], stops[next]);
+                let amount = (position - from.position) / (to.position - from.position);
+                Alpha::from(RGBA8::from(from.color).interpolate(RGBA8::from(to.color), amount))
+            }
+            None => stops
+                .last()
+                .map(|stop| stop.color)
+                .unwrap_or(Alpha::new(HSV::new(), 0.0)),
+        }
+    }
+
+    /// Inserts a stop at `position`, taking the color currently rendered there so that the gradient looks the same.
+    /// Returns the index of the new stop.
+    pub fn add_stop(&mut self, position: f64) -> usize {
+        let color = self.color_at(position);
+        self.stops.push(ColorStop { color, position });
+        self.stops.len() - 1
+    }
+
+    /// Removes the stop at `index`, unless only `MIN_GRADIENT_STOPS` stops are left.
+    pub fn remove_stop(&mut self, index: usize) -> Option<ColorStop> {
+        match index < self.stops.len() && self.stops.len() > MIN_GRADIENT_STOPS {
+            true => Some(self.stops.remove(index)),
+            false => None,
+        }
+    }
+
+    /// The center of the widest gap between two stops or a stop and either end, where a new stop is least in the way.
+    fn free_position(&self) -> f64 {
+        let mut positions = vec![0.0];
+        positions.extend(
+            self.sorted_stops()
+                .iter()
+                .map(|stop| stop.position.clamp(0.0, 1.0)),
+        );
+        positions.push(1.0);
+        positions
+            .windows(2)
+            .fold((0.0, 0.5), |(widest, center), gap| {
+                match gap[1] - gap[0] > widest {
+                    true => (gap[1] - gap[0], (gap[0] + gap[1]) / 2.0),
+                    false => (widest, center),
+                }
+            })
+            .1
+    }
+
+    /// The comma separated color stops, e.g. `#FF0000 0%, #0000FF 100%`.
+    pub fn css_stops(&self) -> String {
+        self.sorted_stops()
+            .iter()
+            .map(|stop| {
+                format!(
+                    "{} {}%",
+                    RGBA8::from(stop.color),
+                    round_to_hundredths(stop.position * 100.0)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn round_to_hundredths(value: f64) -> f64 {
+    // Adding 0.0 turns a negative zero into a positive one.
+    (value * 100.0).round() / 100.0 + 0.0
+}
+
+impl Display for Gradient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            GradientKind::Linear => f.write_fmt(format_args!(
+                "linear-gradient({}deg, {})",
+                round_to_hundredths(self.angle),
+                self.css_stops()
+            )),
+            GradientKind::Radial => f.write_fmt(format_args!(
+                "radial-gradient(circle, {})",
+                self.css_stops()
+            )),
+        }
+    }
+}
+
+/// `gradient` after applying `change` to it.
+fn updated(gradient: Signal<Gradient>, change: impl FnOnce(&mut Gradient)) -> Gradient {
+    let mut gradient = gradient.get_untracked();
+    change(&mut gradient);
+    gradient
+}
+
+/// `gradient` after applying `change` to its stop at `index`, if there is one.
+fn updated_stop(
+    gradient: Signal<Gradient>,
+    index: usize,
+    change: impl FnOnce(&mut ColorStop),
+) -> Gradient {
+    updated(gradient, |gradient| {
+        if let Some(stop) = gradient.stops.get_mut(index) {
+            change(stop);
+        }
+    })
+}
+
+/// Edits a linear or radial `Gradient`.
+/// Stops are dragged along the bar and pressing the bar adds a stop at that position.
+/// The selected stop is edited using a `<ColorPicker>` and can be removed using the delete key.
+#[component]
+pub fn GradientPicker(
+    #[prop(into)] gradient: Signal<Gradient>,
+    #[prop(into)] set_gradient: Out<Gradient>,
+    #[prop(into, optional)] id: Option<AttributeValue>,
+    #[prop(into, optional)] class: Option<AttributeValue>,
+    #[prop(into, optional)] style: Option<AttributeValue>,
+) -> impl IntoView {
+    let direction = use_direction();
+
+    let (selected_index, set_selected_index) = create_signal(0);
+    // Stops may be removed from outside, leaving the selected index behind.
+    let selected = Signal::derive(move || {
+        selected_index
+            .get()
+            .min(gradient.with(|gradient| gradient.stops.len().saturating_sub(1)))
+    });
+    let selected_stop = Signal::derive(move || {
+        gradient.with(|gradient| {
+            gradient
+                .stops
+                .get(selected.get())
+                .copied()
+                .unwrap_or(ColorStop::new(Alpha::new(HSV::new(), 0.0), 0.0))
+        })
+    });
+
+    let add_stop = move |position: f64| {
+        let mut next = gradient.get_untracked();
+        let index = next.add_stop(position);
+        set_gradient.set(next);
+        set_selected_index.set(index);
+        index
+    };
+    let remove_stop = move |index: usize| {
+        let mut next = gradient.get_untracked();
+        if next.remove_stop(index).is_some() {
+            set_gradient.set(next);
+        }
+    };
+    let can_remove =
+        Signal::derive(move || gradient.with(|gradient| gradient.stops.len() > MIN_GRADIENT_STOPS));
+
+    let bar_el: NodeRef<html::Div> = create_node_ref();
+    let bar = TrackedElementClientBoundingRect::new(bar_el);
+    let cursor = RelativeMousePosition::new(bar);
+    let position_from_cursor =
+        create_memo(move |_| direction.get().mirror(cursor.rel_mouse_pos.get().0));
+    let (dragged, set_dragged) = create_signal(None::<usize>);
+
+    // Stop dragging whenever any mouseup event got fired.
+    let GlobalMouseupEvent {
+        read_signal: mouse_up,
+        ..
+    } = expect_context();
+    create_effect(move |_| {
+        if mouse_up.get().is_some() {
+            set_dragged.set(None);
+        }
+    });
+
+    // While a stop is dragged, move it to the cursor.
+    create_effect(move |_| {
+        if let Some(index) = dragged.get() {
+            let position = position_from_cursor.get();
+            set_gradient.set(updated_stop(gradient, index, |stop| {
+                stop.position = position
+            }));
+        }
+    });
+
+    let start_dragging = move |index: usize| {
+        bar.track_client_rect();
+        set_selected_index.set(index);
+        set_dragged.set(Some(index));
+    };
+
+    let render_stop = move |index: usize| {
+        let stop =
+            Signal::derive(move || gradient.with(|gradient| gradient.stops.get(index).copied()));
+        let style = move || {
+            stop.get()
+                .map(|stop| {
+                    format!(
+                        "inset-inline-start: {}%; --gradient-stop-color: {};",
+                        stop.position * 100.0,
+                        RGBA8::from(stop.color)
+                    )
+                })
+                .unwrap_or_default()
+        };
+        view! {
+            <div
+                class="stop"
+                class:selected=move || selected.get() == index
+                class:is-dragged=move || dragged.get() == Some(index)
+                tabindex=0
+                role="slider"
+                aria-valuemin=0
+                aria-valuemax=100
+                aria-valuenow=move || stop.get().map(|stop| round_to_hundredths(stop.position * 100.0))
+                style=style
+                on:focus=move |_| set_selected_index.set(index)
+                // Note: Dragging ends on a global mouseup event, as the cursor may leave the bar while dragging.
+                on:mousedown=move |e| {
+                    e.stop_propagation();
+                    start_dragging(index);
+                }
+                on:touchstart=move |e| {
+                    e.stop_propagation();
+                    start_dragging(index);
+                }
+                on:touchmove=move |e| {
+                    if dragged.get_untracked().is_some() {
+                        e.prevent_default();
+                        e.stop_propagation();
+                    }
+                }
+                on:touchend=move |_e| set_dragged.set(None)
+                on:keydown=move |e: KeyboardEvent| {
+                    let key = e.key();
+                    if matches!(key.as_str(), "Delete" | "Backspace") {
+                        e.prevent_default();
+                        remove_stop(index);
+                    } else if let Some(position) = stop.get_untracked().and_then(|stop| {
+                        value_after_key(&key, stop.position, 0.0, 1.0, None, direction.get_untracked())
+                    }) {
+                        e.prevent_default();
+                        set_gradient.set(updated_stop(gradient, index, |stop| stop.position = position));
+                    }
+                }
+            />
+        }
+    };
+
+    let linear =
+        Signal::derive(move || gradient.with(|gradient| gradient.kind) == GradientKind::Linear);
+
+    view! {
+        <leptonic-gradient-picker id=id class=class style=style>
+            <div class="gradient-preview" style=("--gradient", move || gradient.get().to_string())></div>
+
+            <div
+                class="gradient-bar"
+                node_ref=bar_el
+                style=("--gradient-stops", move || gradient.with(Gradient::css_stops))
+                on:mousedown=move |_e| {
+                    bar.track_client_rect();
+                    let index = add_stop(position_from_cursor.get_untracked());
+                    set_dragged.set(Some(index));
+                }
+            >
+                <For
+                    each=move || 0..gradient.with(|gradient| gradient.stops.len())
+                    key=|index| *index
+                    children=render_stop
+                />
+            </div>
+
+            <div class="gradient-controls">
+                <Select
+                    options=GradientKind::ALL.to_vec()
+                    selected=Signal::derive(move || gradient.with(|gradient| gradient.kind))
+                    set_selected=move |kind: GradientKind| set_gradient.set(updated(gradient, |gradient| gradient.kind = kind))
+                    search_text_provider=move |kind: GradientKind| kind.label().to_owned()
+                    render_option=move |kind: GradientKind| kind.label()
+                    class="gradient-kind"
+                />
+                <Show when=move || linear.get() fallback=move || ()>
+                    <Field>
+                        <FieldLabel>"Angle"</FieldLabel>
+                        <NumberInput min=0.0 max=360.0 step=1.0
+                            get=Signal::derive(move || gradient.with(|gradient| gradient.angle))
+                            set=move |angle: f64| set_gradient.set(updated(gradient, |gradient| gradient.angle = angle))
+                        />
+                    </Field>
+                </Show>
+                <Field>
+                    <FieldLabel>"Position"</FieldLabel>
+                    <NumberInput min=0.0 max=100.0 step=1.0
+                        get=Signal::derive(move || round_to_hundredths(selected_stop.get().position * 100.0))
+                        set=move |percent: f64| set_gradient.set(updated_stop(gradient, selected.get_untracked(), |stop| stop.position = percent / 100.0))
+                    />
+                </Field>
+                <Button
+                    on_click=move |_| { add_stop(gradient.with_untracked(Gradient::free_position)); }
+                    variant=ButtonVariant::Flat
+                    size=ButtonSize::Small
+                >
+                    "Add stop"
+                </Button>
+                <Button
+                    on_click=move |_| remove_stop(selected.get_untracked())
+                    variant=ButtonVariant::Flat
+                    size=ButtonSize::Small
+                    disabled=Signal::derive(move || !can_remove.get())
+                >
+                    "Remove stop"
+                </Button>
+            </div>
+
+            <ColorPicker
+                hsva=Signal::derive(move || selected_stop.get().color)
+                set_hsva={ move |hsva: Alpha<HSV>| set_gradient.set(updated_stop(gradient, selected.get_untracked(), |stop| stop.color = hsva)) }
+            />
+        </leptonic-gradient-picker>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorStop, Gradient};
+    use crate::prelude::{Alpha, HSV, RGB8, RGBA8};
+
+    fn stop(rgba: (u8, u8, u8, u8), position: f64) -> ColorStop {
+        ColorStop::new(RGBA8::from(rgba), position)
+    }
+
+    #[test]
+    fn renders_css_gradients_with_sorted_stops() {
+        let mut gradient = Gradient::linear(
+            45.0,
+            vec![
+                stop((0, 0, 255, 255), 1.0),
+                stop((255, 0, 0, 255), 0.0),
+                stop((0, 255, 0, 128), 1.0 / 3.0),
+            ],
+        );
+        assert_eq!(
+            gradient.to_string(),
+            "linear-gradient(45deg, #FF0000 0%, #00FF0080 33.33%, #0000FF 100%)"
+        );
+        gradient.kind = super::GradientKind::Radial;
+        assert_eq!(
+            gradient.to_string(),
+            "radial-gradient(circle, #FF0000 0%, #00FF0080 33.33%, #0000FF 100%)"
+        );
+    }
+
+    #[test]
+    fn added_stops_keep_the_rendered_color() {
+        let mut gradient = Gradient::linear(
+            90.0,
+            vec![stop((0, 0, 0, 0), 0.0), stop((255, 255, 255, 255), 1.0)],
+        );
+        assert_eq!(gradient.free_position(), 0.5);
+        let index = gradient.add_stop(0.5);
+        assert_eq!(index, 2);
+        assert_eq!(
+            RGBA8::from(gradient.stops[index].color),
+            RGBA8::from((128, 128, 128, 128))
+        );
+        assert_eq!(gradient.free_position(), 0.25);
+        assert_eq!(
+            gradient.color_at(2.0),
+            Alpha::opaque(HSV::from(RGB8::from((255, 255, 255))))
+        );
+    }
+
+    #[test]
+    fn keeps_the_minimum_number_of_stops() {
+        let mut gradient = Gradient::default();
+        gradient.add_stop(0.5);
+        assert!(gradient.remove_stop(3).is_none());
+        assert!(gradient.remove_stop(0).is_some());
+        assert!(gradient.remove_stop(0).is_none());
+        assert_eq!(gradient.stops.len(), super::MIN_GRADIENT_STOPS);
+    }
+}
@@ -16,19 +16,25 @@ pub mod chip;
 pub mod collapsible;
 pub mod color;
 pub mod color_picker;
+pub mod color_swatches;
 pub mod contexts;
 pub mod date_selector;
 pub mod datetime;
 pub mod datetime_input;
+pub mod density;
+pub mod direction;
 pub mod drawer;
 pub mod field;
+pub mod gradient_picker;
 pub mod grid;
 pub mod icon;
 pub mod input;
 pub mod kbd;
 pub mod link;
+pub mod matcher;
 pub mod math;
 pub mod modal;
+pub mod palette;
 pub mod popover;
 pub mod progress_bar;
 pub mod quicksearch;
@@ -209,23 +215,47 @@ pub mod prelude {
     pub use super::collapsible::CollapsibleHeader;
     pub use super::collapsible::Collapsibles;
     pub use super::collapsible::OnOpen;
+    pub use super::color::Alpha;
     pub use super::color::ColorSpace;
+    pub use super::color::Lab;
+    pub use super::color::LinearRGB;
+    pub use super::color::OKLab;
+    pub use super::color::CMYK;
+    pub use super::color::HSL;
     pub use super::color::HSV;
+    pub use super::color::HWB;
+    pub use super::color::LCH;
+    pub use super::color::OKLCH;
     pub use super::color::RGB8;
     pub use super::color::RGBA8;
+    pub use super::color_picker::AlphaSlider;
+    pub use super::color_picker::ColorFormat;
+    pub use super::color_picker::ColorInputs;
     pub use super::color_picker::ColorPalette;
     pub use super::color_picker::ColorPicker;
     pub use super::color_picker::ColorPreview;
     pub use super::color_picker::HueSlider;
+    pub use super::color_swatches::ColorSwatches;
+    pub use super::color_swatches::RecentColors;
+    pub use super::color_swatches::Swatch;
     pub use super::contexts::global_click_event::GlobalClickEvent;
     pub use super::contexts::global_keyboard_event::GlobalKeyboardEvent;
     pub use super::create_signal_ls;
     pub use super::date_selector::DateSelector;
     pub use super::datetime_input::DateTimeInput;
+    pub use super::density::Density;
+    pub use super::density::DensityContext;
+    pub use super::density::DensityProvider;
+    pub use super::direction::Direction;
+    pub use super::direction::DirectionContext;
     pub use super::drawer::Drawer;
     pub use super::drawer::DrawerSide;
     pub use super::field::Field;
     pub use super::field::FieldLabel;
+    pub use super::gradient_picker::ColorStop;
+    pub use super::gradient_picker::Gradient;
+    pub use super::gradient_picker::GradientKind;
+    pub use super::gradient_picker::GradientPicker;
     pub use super::grid::Col;
     pub use super::grid::ColAlign;
     pub use super::grid::Grid;
@@ -242,6 +272,9 @@ pub mod prelude {
     pub use super::link::Link;
     pub use super::link::LinkExt;
     pub use super::link::LinkExtTarget;
+    pub use super::matcher::Highlight;
+    pub use super::matcher::Match;
+    pub use super::matcher::Matcher;
     pub use super::modal::Modal;
     pub use super::modal::ModalBody;
     pub use super::modal::ModalData;
@@ -250,6 +283,7 @@ pub mod prelude {
     pub use super::modal::ModalHeader;
     pub use super::modal::ModalRoot;
     pub use super::modal::ModalTitle;
+    pub use super::palette::Palette;
     pub use super::popover::Popover;
     pub use super::progress_bar::ProgressBar;
     pub use super::quicksearch::Quicksearch;
@@ -260,6 +294,7 @@ pub mod prelude {
     pub use super::root::Root;
     pub use super::safe_html::SafeHtml;
     pub use super::select::Multiselect;
+    pub use super::select::OptionLoader;
     pub use super::select::OptionalSelect;
     pub use super::select::Select;
     pub use super::separator::Separator;
@@ -283,11 +318,17 @@ pub mod prelude {
     pub use super::table::Thead;
     pub use super::table::Tr;
     pub use super::tabs::Tabs;
+    pub use super::theme::ColorScheme;
     pub use super::theme::LeptonicTheme;
     pub use super::theme::Theme;
+    pub use super::theme::ThemeChoice;
+    pub use super::theme::ThemeChoiceSwitch;
     pub use super::theme::ThemeContext;
     pub use super::theme::ThemeProvider;
+    pub use super::theme::ThemeSelect;
     pub use super::theme::ThemeToggle;
+    pub use super::theme::ThemeVariables;
+    pub use super::theme::ThemeVariablesContext;
     pub use super::tile::Tile;
     pub use super::tiptap_editor::TiptapEditor;
     pub use super::toast::Toast;
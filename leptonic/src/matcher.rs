@@ -0,0 +1,277 @@
+use std::{
+    fmt::{Debug, Formatter},
+    ops::Range,
+};
+
+use leptos::*;
+
+const MATCHED_CHAR_SCORE: i64 = 16;
+const WORD_START_BONUS: i64 = 8;
+const CONSECUTIVE_BONUS: i64 = 8;
+const TEXT_START_BONUS: i64 = 16;
+const WHOLE_TEXT_BONUS: i64 = 32;
+
+/// How a search text is matched against the searchable text of an option. Case is ignored.
+#[derive(Clone, Copy, Default)]
+pub enum Matcher {
+    /// The search text occurs anywhere in the text.
+    #[default]
+    Substring,
+    /// The text starts with the search text.
+    Prefix,
+    /// The characters of the search text occur in the text in the same order, e.g. "bkmk" matches "Bookmark".
+    Fuzzy,
+    /// Matches using the given callback, receiving the search text and the text to search in.
+    Custom(Callback<(String, String), Option<Match>>),
+}
+
+/// How well a text matched a search text.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Match {
+    /// Higher scores are more relevant. Matches at the start of words, of consecutive characters and of the whole text score higher.
+    pub score: i64,
+    /// Byte ranges of the matched characters in the text, ascending and not overlapping.
+    pub ranges: Vec<Range<usize>>,
+}
+
+impl Debug for Matcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Matcher::Substring => f.write_str("Substring"),
+            Matcher::Prefix => f.write_str("Prefix"),
+            Matcher::Fuzzy => f.write_str("Fuzzy"),
+            Matcher::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl Matcher {
+    /// Matches `search` against `text`. An empty search matches every text, with a score of zero and nothing to highlight.
+    pub fn matches(&self, search: &str, text: &str) -> Option<Match> {
+        if search.is_empty() {
+            return Some(Match::default());
+        }
+        if let Matcher::Custom(matches) = self {
+            return matches.call((search.to_owned(), text.to_owned()));
+        }
+
+        let search = search.chars().map(fold).collect::<Vec<_>>();
+        let chars = text.char_indices().collect::<Vec<_>>();
+        let folded = chars.iter().map(|(_, c)| fold(*c)).collect::<Vec<_>>();
+        if search.len() > folded.len() {
+            return None;
+        }
+
+        let starts = match self {
+            Matcher::Prefix => 0..1,
+            _ => 0..folded.len() - search.len() + 1,
+        };
+        let positions = starts.filter_map(|start| match self {
+            Matcher::Fuzzy => subsequence_from(&search, &folded, start),
+            _ => (folded[start..start + search.len()] == search[..])
+                .then(|| (start..start + search.len()).collect()),
+        });
+
+        let mut best: Option<(i64, Vec<usize>)> = None;
+        for positions in positions {
+            let score = score(&positions, &chars);
+            match &best {
+                Some((best_score, _)) if *best_score >= score => {}
+                _ => best = Some((score, positions)),
+            }
+        }
+        best.map(|(score, positions)| Match {
+            score,
+            ranges: byte_ranges(&positions, text, &chars),
+        })
+    }
+
+    /// The items whose text matches `search`, most relevant first. Items matching equally well keep their order.
+    pub fn filter<T>(
+        &self,
+        search: &str,
+        items: impl IntoIterator<Item = T>,
+        text_of: impl Fn(&T) -> String,
+    ) -> Vec<(T, Match)> {
+        let mut matched = items
+            .into_iter()
+            .filter_map(|item| {
+                let found = self.matches(search, &text_of(&item))?;
+                Some((item, found))
+            })
+            .collect::<Vec<_>>();
+        matched.sort_by_key(|(_, found)| std::cmp::Reverse(found.score));
+        matched
+    }
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Positions of the search characters when greedily matching them, starting with the first one at `start`.
+fn subsequence_from(search: &[char], text: &[char], start: usize) -> Option<Vec<usize>> {
+    if text[start] != search[0] {
+        return None;
+    }
+    let mut positions = vec![start];
+    let mut next = start + 1;
+    for c in &search[1..] {
+        let position = next + text[next..].iter().position(|it| it == c)?;
+        positions.push(position);
+        next = position + 1;
+    }
+    Some(positions)
+}
+
+fn score(positions: &[usize], chars: &[(usize, char)]) -> i64 {
+    let mut score = 0;
+    for (i, position) in positions.iter().copied().enumerate() {
+        score += MATCHED_CHAR_SCORE;
+        if is_word_start(chars, position) {
+            score += WORD_START_BONUS;
+        }
+        if i > 0 && positions[i - 1] + 1 == position {
+            score += CONSECUTIVE_BONUS;
+        }
+    }
+    if let (Some(first), Some(last)) = (positions.first(), positions.last()) {
+        if *first == 0 {
+            score += TEXT_START_BONUS;
+        }
+        if positions.len() == chars.len() {
+            score += WHOLE_TEXT_BONUS;
+        }
+        // Skipped characters make a match less relevant.
+        score -= (last - first + 1 - positions.len()) as i64;
+    }
+    score
+}
+
+fn is_word_start(chars: &[(usize, char)], position: usize) -> bool {
+    let current = chars[position].1;
+    match position.checked_sub(1).map(|previous| chars[previous].1) {
+        None => true,
+        Some(previous) => {
+            !previous.is_alphanumeric() || (previous.is_lowercase() && current.is_uppercase())
+        }
+    }
+}
+
+fn byte_ranges(positions: &[usize], text: &str, chars: &[(usize, char)]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for position in positions.iter().copied() {
+        let start = chars[position].0;
+        let end = chars.get(position + 1).map_or(text.len(), |(end, _)| *end);
+        match ranges.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ => ranges.push(start..end),
+        }
+    }
+    ranges
+}
+
+/// Splits `text` into consecutive parts, telling whether each part lies within `ranges`.
+/// Ranges not lying on character boundaries of `text` are ignored.
+fn segments<'a>(text: &'a str, ranges: &[Range<usize>]) -> Vec<(&'a str, bool)> {
+    let mut segments = Vec::new();
+    let mut end = 0;
+    for range in ranges {
+        if range.start < end || text.get(range.clone()).is_none() {
+            continue;
+        }
+        if range.start > end {
+            segments.push((&text[end..range.start], false));
+        }
+        if !range.is_empty() {
+            segments.push((&text[range.clone()], true));
+        }
+        end = range.end;
+    }
+    if end < text.len() {
+        segments.push((&text[end..], false));
+    }
+    segments
+}
+
+/// Renders `text`, marking the characters in `ranges`. Use it to highlight the ranges of a `Match`.
+#[component]
+pub fn Highlight(
+    #[prop(into)] text: String,
+    #[prop(into, optional)] ranges: Vec<Range<usize>>,
+    #[prop(into, optional)] id: Option<AttributeValue>,
+    #[prop(into, optional)] class: Option<AttributeValue>,
+    #[prop(into, optional)] style: Option<AttributeValue>,
+) -> impl IntoView {
+    let segments = segments(&text, &ranges)
+        .into_iter()
+        .map(|(segment, marked)| match marked {
+            true => view! { <mark>{segment.to_owned()}</mark> }.into_view(),
+            false => segment.to_owned().into_view(),
+        })
+        .collect_view();
+
+    view! {
+        <leptonic-highlight id=id class=class style=style>
+            {segments}
+        </leptonic-highlight>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{segments, Match, Matcher};
+
+    fn ranked(matcher: Matcher, search: &str, texts: &[&'static str]) -> Vec<&'static str> {
+        matcher
+            .filter(search, texts.iter().copied(), |text| (*text).to_owned())
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect()
+    }
+
+    #[test]
+    fn substring_matches_rank_word_starts_first() {
+        let texts = ["Peru", "Russia", "Prussia", "Rust"];
+        assert_eq!(
+            ranked(Matcher::Substring, "rus", &texts),
+            vec!["Russia", "Rust", "Prussia"]
+        );
+        assert_eq!(
+            ranked(Matcher::Prefix, "rus", &texts),
+            vec!["Russia", "Rust"]
+        );
+        assert_eq!(ranked(Matcher::Substring, "", &texts), texts.to_vec());
+    }
+
+    #[test]
+    fn fuzzy_matches_subsequences() {
+        let found = Matcher::Fuzzy.matches("bkmk", "Bookmark").unwrap();
+        assert_eq!(found.ranges, vec![0..1, 3..5, 7..8]);
+        assert!(Matcher::Fuzzy.matches("kb", "Bookmark").is_none());
+        assert_eq!(
+            ranked(Matcher::Fuzzy, "sp", &["Inspect", "Select Picker", "Spain"]),
+            vec!["Spain", "Select Picker", "Inspect"]
+        );
+    }
+
+    #[test]
+    fn ranges_are_byte_ranges_ignoring_case() {
+        let found = Matcher::Substring.matches("ÖL", "Heizöl").unwrap();
+        assert_eq!(found.ranges, vec![4..7]);
+        assert_eq!(&"Heizöl"[4..7], "öl");
+        assert_eq!(Matcher::Prefix.matches("", "any"), Some(Match::default()));
+    }
+
+    #[test]
+    fn segments_mark_ranges() {
+        assert_eq!(
+            segments("Bookmark", &[0..1, 3..5]),
+            vec![("B", true), ("oo", false), ("km", true), ("ark", false)]
+        );
+        assert_eq!(
+            segments("öl", &[0..1, 2..3]),
+            vec![("ö", false), ("l", true)]
+        );
+    }
+}
@@ -0,0 +1 @@
+pub use leptonic_theme::palette::*;
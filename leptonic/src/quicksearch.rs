@@ -5,6 +5,9 @@ use leptos::*;
 pub fn Quicksearch(
     #[prop(into)] trigger: ViewCallback<WriteSignal<bool>>,
     #[prop(into)] query: Callback<String, Vec<QuicksearchOption>>,
+    /// Filters the options returned by `query` by matching the search against their labels, most relevant first.
+    #[prop(optional)]
+    matcher: Option<Matcher>,
     #[prop(into, optional)] id: Option<AttributeValue>,
     #[prop(into, optional)] class: Option<AttributeValue>,
     #[prop(into, optional)] style: Option<AttributeValue>,
@@ -16,6 +19,7 @@ pub fn Quicksearch(
             <QuicksearchModal
                 show_when=show_modal
                 query=query
+                matcher=matcher
                 on_cancel=move || set_show_modal.set(false)
             />
         </leptonic-quicksearch>
@@ -48,13 +52,25 @@ pub struct QuicksearchOption {
 fn QuicksearchModal(
     #[prop(into)] show_when: Signal<bool>,
     #[prop(into)] query: Callback<String, Vec<QuicksearchOption>>,
+    matcher: Option<Matcher>,
     #[prop(into)] on_cancel: Producer<()>,
 ) -> impl IntoView {
     let on_cancel = StoredValue::new(on_cancel);
 
     let (input, set_input) = create_signal("".to_owned());
 
-    let options = move || query.call(input.get());
+    let options = move || {
+        let input = input.get();
+        let options = query.call(input.clone());
+        match matcher {
+            Some(matcher) => matcher
+                .filter(&input, options, |option| option.label.to_string())
+                .into_iter()
+                .map(|(option, _)| option)
+                .collect(),
+            None => options,
+        }
+    };
 
     let g_keyboard_event: GlobalKeyboardEvent = expect_context::<GlobalKeyboardEvent>();
     create_effect(move |_old| {
@@ -12,6 +12,7 @@ use crate::{
         global_scroll_event::GlobalScrollEvent,
     },
     prelude::*,
+    theme::active_theme,
 };
 
 /// Leptonic's root context. Always available in components under <Root>.
@@ -27,8 +28,18 @@ pub struct Leptonic {
 }
 
 // Note(lukas): We accept the generic, as applications will typically only use this component once and will never suffer from monomorphization code bloat.
+/// The theme choice is persisted in local storage. Set `follow_system` to follow the operating systems color scheme
+/// until the user explicitly chooses a theme, instead of starting out with `default_theme`.
+/// The `direction` is applied to the document and provided to all components as a `DirectionContext`.
+/// The `density` applies to the whole application, but can be changed for any subtree using a <DensityProvider>.
 #[component]
-pub fn Root<T>(default_theme: T, children: Children) -> impl IntoView
+pub fn Root<T>(
+    default_theme: T,
+    #[prop(optional)] follow_system: bool,
+    #[prop(into, optional)] direction: MaybeSignal<Direction>,
+    #[prop(into, optional)] density: MaybeSignal<Density>,
+    children: Children,
+) -> impl IntoView
 where
     T: Theme + 'static,
 {
@@ -180,17 +191,71 @@ where
         is_desktop_device: Signal::derive(move || !is_mobile_device.get()),
     });
 
+    let direction = Signal::derive(move || direction.get());
+    provide_context(DirectionContext { direction });
+    apply_direction(direction);
+
+    let default_choice = match follow_system {
+        true => ThemeChoice::System,
+        false => ThemeChoice::Theme(default_theme),
+    };
+    let (choice, set_choice) = create_signal_ls("theme", default_choice);
+    apply_color_scheme(active_theme(choice.into()));
+
     // NOTE: --leptonic-vh can be used like this in CSS code: height: var(--leptonic-vh, 100vh);
 
     view! {
-        <ThemeProvider theme=create_signal_ls("theme", default_theme)>
-            <ToastRoot>
-                <ModalRoot>
-                    <Box style="min-height: 100%; min-width: 100%; display: flex; flex-direction: column;">
-                        { children() }
-                    </Box>
-                </ModalRoot>
-            </ToastRoot>
+        <ThemeProvider choice=(choice, set_choice)>
+            <DensityProvider density=density>
+                <ToastRoot>
+                    <ModalRoot>
+                        <Box style="min-height: 100%; min-width: 100%; display: flex; flex-direction: column;">
+                            { children() }
+                        </Box>
+                    </ModalRoot>
+                </ToastRoot>
+            </DensityProvider>
         </ThemeProvider>
     }
 }
+
+/// Keeps the `color-scheme` of the document element in sync with the active theme,
+/// so that scrollbars and native form controls match it.
+fn apply_color_scheme<T: Theme + 'static>(theme: Signal<T>) {
+    use std::ops::Deref;
+
+    create_effect(move |_| {
+        let color_scheme = theme.get().color_scheme();
+        if let Some(document) = use_document().deref() {
+            let result = document
+                .document_element()
+                .map(|element| element.unchecked_into::<web_sys::HtmlElement>())
+                .map(|element| {
+                    element
+                        .style()
+                        .set_property("color-scheme", color_scheme.as_str())
+                });
+            if let Some(Err(err)) = result {
+                tracing::warn!(?err, "Could not set color-scheme");
+            }
+        }
+    });
+}
+
+/// Keeps the `dir` attribute of the document element in sync with the given direction,
+/// so that the logical properties used throughout the styles mirror accordingly.
+fn apply_direction(direction: Signal<Direction>) {
+    use std::ops::Deref;
+
+    create_effect(move |_| {
+        let direction = direction.get();
+        if let Some(document) = use_document().deref() {
+            let result = document
+                .document_element()
+                .map(|element| element.set_attribute("dir", direction.as_str()));
+            if let Some(Err(err)) = result {
+                tracing::warn!(?err, "Could not set dir");
+            }
+        }
+    });
+}
@@ -1,15 +1,26 @@
-use std::fmt::Debug;
+use std::{
+    fmt::{Debug, Display},
+    future::Future,
+    ops::Range,
+    pin::Pin,
+};
 
 use leptos::*;
 use leptos_icons::BsIcon;
-use web_sys::{HtmlElement, KeyboardEvent, MouseEvent};
+use web_sys::{ClipboardEvent, HtmlElement, KeyboardEvent, MouseEvent};
 
 use crate::prelude::*;
 
+#[deprecated(
+    note = "Use a `Matcher` and the `search_text_provider` prop instead. Will be removed in the next release."
+)]
 pub trait SearchTextProvider {
     fn get_searchable_content() -> String;
 }
 
+#[deprecated(
+    note = "Use a `Matcher` and the `search_text_provider` prop instead. Will be removed in the next release."
+)]
 pub trait SelectSearchable {
     fn get_searchable_content() -> String;
 
@@ -22,61 +33,513 @@ pub trait SelectOption: Debug + Clone + PartialEq + Eq {}
 
 impl<T: Debug + Clone + PartialEq + Eq> SelectOption for T {}
 
-// TODO: select_previous and select_next could be made more efficient.
-// If we would know that the initial vec from which the current preselect'ed option was taken didn't change
-// and if we also keep track of the index of this option in the vec, we can just read the previous / next option
-// be decrementing or incrementing the old index!
+type LoadedOptions<O> = Pin<Box<dyn Future<Output = Result<Vec<O>, String>>>>;
+
+/// Loads the options matching a search text, e.g. by querying a server.
+/// Use it instead of filtering `options` in memory when there are too many options to hold on to.
+pub struct OptionLoader<O: 'static> {
+    load: StoredValue<Box<dyn Fn(String) -> LoadedOptions<O>>>,
+    debounce_ms: f64,
+}
+
+impl<O: 'static> OptionLoader<O> {
+    /// Options are loaded once the user stopped typing for 250 milliseconds. Errors are displayed to the user.
+    pub fn new<F, Fut, E>(load: F) -> Self
+    where
+        F: Fn(String) -> Fut + 'static,
+        Fut: Future<Output = Result<Vec<O>, E>> + 'static,
+        E: Display,
+    {
+        Self {
+            load: store_value(Box::new(move |search| {
+                let response = load(search);
+                Box::pin(async move { response.await.map_err(|err| err.to_string()) })
+                    as LoadedOptions<O>
+            })),
+            debounce_ms: 250.0,
+        }
+    }
+
+    /// Time in milliseconds the search text must stay unchanged before options are loaded.
+    pub fn debounce(mut self, ms: f64) -> Self {
+        self.debounce_ms = ms;
+        self
+    }
+
+    fn load(&self, search: String) -> LoadedOptions<O> {
+        self.load.with_value(|load| load(search))
+    }
+}
+
+impl<O: 'static> Copy for OptionLoader<O> {}
+
+impl<O: 'static> Clone for OptionLoader<O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O: 'static> Debug for OptionLoader<O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OptionLoader")
+            .field("debounce_ms", &self.debounce_ms)
+            .finish()
+    }
+}
+
+impl<O, F, Fut, E> From<F> for OptionLoader<O>
+where
+    O: 'static,
+    F: Fn(String) -> Fut + 'static,
+    Fut: Future<Output = Result<Vec<O>, E>> + 'static,
+    E: Display,
+{
+    fn from(load: F) -> Self {
+        OptionLoader::new(load)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LoadState {
+    Idle,
+    Loading,
+    Failed(String),
+}
+
+/// The options offered for the current search text, along with how their search text matched it.
+/// Without a loader, `options` are filtered in memory using `matcher`, most relevant first.
+/// Otherwise, `options` (tracking their changes) are only shown until the loader first responded and new options are
+/// loaded whenever the search text changed or the options are shown again. Loaded options keep their order, `matcher` is only used to highlight them.
+/// Only the response to the latest request is used, responses to previous search texts are ignored.
+/// With a `group_provider`, options of the same group are moved next to each other.
+fn create_searched_options<O: SelectOption + 'static>(
+    options: MaybeSignal<Vec<O>>,
+    loader: Option<OptionLoader<O>>,
+    search: ReadSignal<String>,
+    search_text_provider: Callback<O, String>,
+    matcher: Matcher,
+    group_provider: Option<Callback<O, String>>,
+    show_options: ReadSignal<bool>,
+) -> (Memo<Vec<O>>, Memo<Vec<Match>>, ReadSignal<LoadState>) {
+    let text_of = move |option: &O| search_text_provider.call(option.clone());
+
+    let (load_state, set_load_state) = create_signal(LoadState::Idle);
+
+    let matched = match loader {
+        None => create_memo(move |_| {
+            search.with(|search| {
+                options.with(|options| matcher.filter(search, options.iter().cloned(), text_of))
+            })
+        }),
+        Some(loader) => {
+            let (loaded_options, set_loaded_options) = create_signal(options.get_untracked());
+            let latest_request = store_value(0_u64);
+            let responded = store_value(false);
+
+            create_effect(move |_| {
+                let options = options.get();
+                if !responded.get_value() {
+                    set_loaded_options.set(options);
+                }
+            });
+
+            let _ = leptos_use::watch_debounced(
+                move || (search.get(), show_options.get()),
+                move |(search, shown), _, _| {
+                    if !*shown {
+                        return;
+                    }
+                    latest_request.update_value(|id| *id += 1);
+                    let request = latest_request.get_value();
+                    set_load_state.set(LoadState::Loading);
+                    let response = loader.load(search.clone());
+                    spawn_local(async move {
+                        let result = response.await;
+                        // A newer request was made or this component was already disposed.
+                        if latest_request.try_get_value() != Some(request) {
+                            return;
+                        }
+                        match result {
+                            Ok(options) => {
+                                responded.set_value(true);
+                                set_loaded_options.set(options);
+                                set_load_state.set(LoadState::Idle);
+                            }
+                            Err(err) => set_load_state.set(LoadState::Failed(err)),
+                        }
+                    });
+                },
+                loader.debounce_ms,
+            );
+
+            create_memo(move |_| {
+                search.with(|search| {
+                    loaded_options.with(|options| {
+                        options
+                            .iter()
+                            .map(|option| {
+                                let found = matcher.matches(search, &text_of(option));
+                                (option.clone(), found.unwrap_or_default())
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+            })
+        }
+    };
+
+    let grouped = create_memo(move |_| {
+        let matched = matched.get();
+        match group_provider {
+            Some(group_provider) => {
+                group_options(matched, |(option, _)| group_provider.call(option.clone()))
+            }
+            None => matched,
+        }
+    });
+
+    (
+        create_memo(move |_| {
+            grouped.with(|grouped| grouped.iter().map(|(option, _)| option.clone()).collect())
+        }),
+        create_memo(move |_| {
+            grouped.with(|grouped| grouped.iter().map(|(_, found)| found.clone()).collect())
+        }),
+        load_state,
+    )
+}
+
+fn render_load_state(load_state: ReadSignal<LoadState>) -> impl IntoView {
+    move || match load_state.get() {
+        LoadState::Idle => ().into_view(),
+        LoadState::Loading => view! {
+            <leptonic-select-loading>"Loading..."</leptonic-select-loading>
+        }
+        .into_view(),
+        LoadState::Failed(err) => view! {
+            <leptonic-select-load-error>{err}</leptonic-select-load-error>
+        }
+        .into_view(),
+    }
+}
+
+/// Stably moves options of the same group next to each other. Groups keep the order of their first option.
+fn group_options<O>(options: Vec<O>, group_of: impl Fn(&O) -> String) -> Vec<O> {
+    let mut groups: Vec<(String, Vec<O>)> = Vec::new();
+    for option in options {
+        let group = group_of(&option);
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, members)) => members.push(option),
+            None => groups.push((group, vec![option])),
+        }
+    }
+    groups
+        .into_iter()
+        .flat_map(|(_, members)| members)
+        .collect()
+}
+
+/// A line of the option list. Options are identified by their index in the offered options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Row {
+    Group(String),
+    Option(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rows {
+    rows: Vec<Row>,
+    /// The row of each option.
+    option_rows: Vec<usize>,
+}
+
+impl Rows {
+    fn ungrouped(len: usize) -> Self {
+        Self {
+            rows: (0..len).map(Row::Option).collect(),
+            option_rows: (0..len).collect(),
+        }
+    }
+
+    /// Puts a header in front of every group, given the group of each of the already grouped options.
+    /// Groups without options never get a header, so that searching hides empty groups.
+    fn grouped(groups: impl Iterator<Item = String>) -> Self {
+        let mut rows = Vec::new();
+        let mut option_rows = Vec::new();
+        let mut current: Option<String> = None;
+        for (index, group) in groups.enumerate() {
+            if current.as_ref() != Some(&group) {
+                rows.push(Row::Group(group.clone()));
+                current = Some(group);
+            }
+            option_rows.push(rows.len());
+            rows.push(Row::Option(index));
+        }
+        Self { rows, option_rows }
+    }
+}
+
+/// Characters separating the terms of text pasted into a creatable select.
+const TERM_SEPARATORS: [char; 3] = [',', '\n', '\r'];
+
+/// The trimmed, non-empty and distinct terms of `input`, in order of their first occurrence.
+fn split_terms(input: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for term in input.split(TERM_SEPARATORS).map(str::trim) {
+        if !term.is_empty() && !terms.iter().any(|it| it.eq_ignore_ascii_case(term)) {
+            terms.push(term.to_owned());
+        }
+    }
+    terms
+}
 
 // TODO: Prop: close_options_menu_on_selection: bool
 // TODO: Prop: selection_changed: Callback<Selection<T>>
 // TODO: multiselect deselect performance
 // TODO: remove code duplication between select variants
 
-// TODO: Replace select_previous and select_next with a function that stores the current index and does not need to traverse on each call!
+/// An option preselected by hovering it or through keyboard navigation, identified by its index in the offered options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Preselected {
+    index: usize,
+    /// Options preselected using the keyboard are scrolled into view.
+    by_keyboard: bool,
+}
 
-fn select_previous<O: SelectOption + 'static>(
-    available: &[O],
-    preselected: ReadSignal<Option<O>>,
-    set_preselected: WriteSignal<Option<O>>,
-) {
-    let previous = preselected.with_untracked(|current| match current {
-        Some(current) => match available.iter().position(|it| it == current) {
-            Some(current_pos) => match current_pos >= 1 {
-                true => Some(available[current_pos - 1].clone()),
-                false => available.last().cloned(),
-            },
-            None => available.last().cloned(),
-        },
-        None => available.last().cloned(),
-    });
-    set_preselected.set(previous);
+impl Preselected {
+    fn keyboard(index: usize) -> Self {
+        Self {
+            index,
+            by_keyboard: true,
+        }
+    }
+
+    fn pointer(index: usize) -> Self {
+        Self {
+            index,
+            by_keyboard: false,
+        }
+    }
 }
 
-fn select_next<O: SelectOption + 'static>(
-    available: &[O],
-    preselected: ReadSignal<Option<O>>,
-    set_preselected: WriteSignal<Option<O>>,
-) {
-    let next = preselected.with_untracked(|current| match current {
-        Some(current) => match available.iter().position(|it| it == current) {
-            Some(current_pos) => match (current_pos + 1) < available.len() {
-                true => Some(available[current_pos + 1].clone()),
-                false => available.first().cloned(),
-            },
-            None => available.first().cloned(),
-        },
-        None => available.first().cloned(),
+/// The index to preselect when pressing ArrowUp, wrapping around to the last option.
+fn previous_index(current: Option<usize>, len: usize) -> Option<usize> {
+    match current {
+        _ if len == 0 => None,
+        Some(current) if current >= 1 && current < len => Some(current - 1),
+        _ => Some(len - 1),
+    }
+}
+
+/// The index to preselect when pressing ArrowDown, wrapping around to the first option.
+fn next_index(current: Option<usize>, len: usize) -> Option<usize> {
+    match current {
+        _ if len == 0 => None,
+        Some(current) if current + 1 < len => Some(current + 1),
+        _ => Some(0),
+    }
+}
+
+/// Options rendered above and below the visible ones, so that scrolling does not reveal missing options.
+const OVERSCAN: usize = 5;
+
+/// Height in pixels assumed for an option until a rendered one was measured.
+const ESTIMATED_OPTION_HEIGHT: f64 = 36.0;
+
+/// Indices of the options to render, given the scroll position and height of the list in pixels.
+fn visible_range(
+    scroll_top: f64,
+    viewport_height: f64,
+    option_height: f64,
+    len: usize,
+) -> Range<usize> {
+    let first = (scroll_top / option_height).floor().max(0.0) as usize;
+    let last = ((scroll_top + viewport_height) / option_height)
+        .ceil()
+        .max(0.0) as usize;
+    first.saturating_sub(OVERSCAN).min(len)..last.saturating_add(OVERSCAN).min(len)
+}
+
+/// The scroll position at which the option at `index` is fully visible, or `None` if it already is.
+fn scroll_top_revealing(
+    index: usize,
+    option_height: f64,
+    scroll_top: f64,
+    viewport_height: f64,
+) -> Option<f64> {
+    let top = index as f64 * option_height;
+    let bottom = top + option_height;
+    if top < scroll_top {
+        Some(top)
+    } else if bottom > scroll_top + viewport_height {
+        Some(bottom - viewport_height)
+    } else {
+        None
+    }
+}
+
+/// Renders only the options scrolled into view, so that selects with thousands of options stay responsive.
+/// Options and group headers are given a fixed height (see select.scss), which is measured from the first rendered option.
+#[component]
+fn SelectOptionList<O>(
+    options: Memo<Vec<O>>,
+    /// How the search text of each option matched the search.
+    matches: Memo<Vec<Match>>,
+    group_provider: Option<Callback<O, String>>,
+    preselected: ReadSignal<Option<Preselected>>,
+    set_preselected: WriteSignal<Option<Preselected>>,
+    render_option: StoredValue<ViewCallback<O>>,
+    render_match: Option<ViewCallback<(O, Match)>>,
+    is_selected: Callback<O, bool>,
+    is_disabled: Callback<O, bool>,
+    select: StoredValue<Callback<O>>,
+) -> impl IntoView
+where
+    O: SelectOption + 'static,
+{
+    let list: NodeRef<html::Div> = create_node_ref();
+    let (scroll_top, set_scroll_top) = create_signal(0.0);
+    let (viewport_height, set_viewport_height) = create_signal(0.0);
+    let (option_height, set_option_height) = create_signal(ESTIMATED_OPTION_HEIGHT);
+
+    let measure = move || {
+        if let Some(list) = list.get_untracked() {
+            set_scroll_top.set(list.scroll_top() as f64);
+            set_viewport_height.set(list.client_height() as f64);
+            if let Ok(Some(option)) = list.query_selector("leptonic-select-option") {
+                let height = option.get_bounding_client_rect().height();
+                if height > 0.0 && height != option_height.get_untracked() {
+                    set_option_height.set(height);
+                }
+            }
+        }
+    };
+
+    let rows = create_memo(move |_| {
+        options.with(|options| match group_provider {
+            Some(group_provider) => Rows::grouped(
+                options
+                    .iter()
+                    .map(|option| group_provider.call(option.clone())),
+            ),
+            None => Rows::ungrouped(options.len()),
+        })
     });
-    set_preselected.set(next);
+
+    // Changed options are laid out in the next frame.
+    create_effect(move |_| {
+        options.track();
+        if let Ok(handle) = request_animation_frame_with_handle(measure) {
+            on_cleanup(move || handle.cancel());
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(Preselected {
+            index,
+            by_keyboard: true,
+        }) = preselected.get()
+        {
+            let row = rows.with_untracked(|rows| rows.option_rows.get(index).copied());
+            if let (Some(list), Some(row)) = (list.get_untracked(), row) {
+                if let Some(top) = scroll_top_revealing(
+                    row,
+                    option_height.get_untracked(),
+                    list.scroll_top() as f64,
+                    list.client_height() as f64,
+                ) {
+                    list.set_scroll_top(top.round() as i32);
+                }
+            }
+        }
+    });
+
+    let len = move || rows.with(|rows| rows.rows.len());
+    let range = create_memo(move |_| {
+        visible_range(
+            scroll_top.get(),
+            viewport_height.get(),
+            option_height.get(),
+            len(),
+        )
+    });
+
+    let render = move |index: usize, option: O| {
+        let clone1 = option.clone();
+        let clone2 = option.clone();
+        let clone3 = option.clone();
+        let found = matches.with_untracked(|matches| matches.get(index).cloned());
+        let content = match (render_match, found) {
+            (Some(render_match), Some(found)) => render_match.call((clone3, found)),
+            _ => render_option.get_value().call(clone3),
+        };
+        view! {
+            <leptonic-select-option
+                class:preselected=move || preselected.with(|preselected| preselected.map(|it| it.index) == Some(index))
+                class:selected=move || is_selected.call(option.clone())
+                class:disabled=move || is_disabled.call(clone1.clone())
+                on:mouseenter=move |_e| set_preselected.set(Some(Preselected::pointer(index)))
+                on:click=move |_e| {
+                    if !untrack(|| is_disabled.call(clone2.clone())) {
+                        select.get_value().call(clone2.clone())
+                    }
+                }
+            >
+                { content }
+            </leptonic-select-option>
+        }
+    };
+
+    view! {
+        <div class="leptonic-select-option-list" node_ref=list on:scroll=move |_| measure()>
+            <div class="leptonic-select-option-spacer" style:height=move || format!("{}px", len() as f64 * option_height.get())>
+                <div class="leptonic-select-option-window" style:top=move || format!("{}px", range.get().start as f64 * option_height.get())>
+                    { move || {
+                        let range = range.get();
+                        // Options are rendered again when only their highlighted characters changed.
+                        matches.track();
+                        rows.with(|rows| rows.rows[range].to_vec())
+                            .into_iter()
+                            .map(|row| match row {
+                                Row::Group(group) => view! {
+                                    <leptonic-select-group>{group}</leptonic-select-group>
+                                }
+                                .into_view(),
+                                Row::Option(index) => options
+                                    .with(|options| options.get(index).cloned())
+                                    .map(|option| render(index, option))
+                                    .into_view(),
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </div>
+        </div>
+    }
 }
 
 #[component]
 pub fn Select<O>(
-    #[prop(into)] options: MaybeSignal<Vec<O>>,
+    /// All options, or the options shown until `loader` first responded.
+    #[prop(into, optional)]
+    options: MaybeSignal<Vec<O>>,
+    /// Loads the options matching the search text instead of filtering `options`.
+    #[prop(into, optional)]
+    loader: Option<OptionLoader<O>>,
     #[prop(into)] selected: Signal<O>,
     #[prop(into)] set_selected: Out<O>,
     #[prop(into)] search_text_provider: Callback<O, String>,
+    /// Matches the search text against the search text of each option. Defaults to a substring search.
+    #[prop(optional)]
+    matcher: Matcher,
+    /// Shows options under the header of the group returned for them. Options of the same group are shown together.
+    #[prop(into, optional)]
+    group_provider: Option<Callback<O, String>>,
     #[prop(into)] render_option: ViewCallback<O>,
+    /// Renders an offered option along with how its search text matched the search, e.g. to `<Highlight>` the matched characters.
+    /// Used instead of `render_option` in the list of offered options.
+    #[prop(into, optional)]
+    render_match: Option<ViewCallback<(O, Match)>>,
     #[prop(into, optional)] autofocus_search: Option<Signal<bool>>,
     #[prop(into, optional)] class: Option<AttributeValue>,
     #[prop(into, optional)] style: Option<AttributeValue>,
@@ -100,28 +563,30 @@ where
         Signal::derive(move || show_options.get() && autofocus_search.get());
     let (search_is_focused, set_search_is_focused) = create_signal(false);
 
-    let stored_options = store_value(options);
-    let (preselected, set_preselected) = create_signal(Option::<O>::None);
-    let memoized_preselected = create_memo(move |_| preselected.get());
+    let (preselected, set_preselected) = create_signal(Option::<Preselected>::None);
 
     let (search, set_search) = create_signal("".to_owned());
 
-    let filtered_options = create_memo(move |_| {
-        let lowercased_search = search.get().to_lowercase();
-        stored_options
-            .get_value()
-            .get()
-            .into_iter()
-            .filter(|it| {
-                search_text_provider
-                    .call(it.clone())
-                    .to_lowercase()
-                    .contains(lowercased_search.as_str())
-            })
-            .collect::<Vec<O>>()
+    let (filtered_options, matches, load_state) = create_searched_options(
+        options,
+        loader,
+        search,
+        search_text_provider,
+        matcher,
+        group_provider,
+        show_options,
+    );
+
+    let has_options = create_memo(move |_| {
+        !filtered_options.with(|options| options.is_empty())
+            || load_state.with(|state| *state != LoadState::Idle)
     });
 
-    let has_options = create_memo(move |_| !filtered_options.with(|options| options.is_empty()));
+    // Indices into previously offered options are meaningless.
+    create_effect(move |_| {
+        filtered_options.track();
+        set_preselected.set(None);
+    });
 
     let select = StoredValue::new(Callback::new(move |option: O| {
         set_selected.set(option);
@@ -155,27 +620,25 @@ where
                 "ArrowUp" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    // TODO: Use options_available_for_preselect.with_untracked when https://github.com/leptos-rs/leptos/issues/1212 is resolved and released.
-                    select_previous(
-                        &filtered_options.get_untracked(),
-                        preselected,
-                        set_preselected,
-                    );
+                    let len = filtered_options.with_untracked(Vec::len);
+                    let current = preselected.get_untracked().map(|it| it.index);
+                    set_preselected.set(previous_index(current, len).map(Preselected::keyboard));
                 }
                 "ArrowDown" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    // TODO: Use options_available_for_preselect.with_untracked when https://github.com/leptos-rs/leptos/issues/1212 is resolved and released.
-                    select_next(
-                        &filtered_options.get_untracked(),
-                        preselected,
-                        set_preselected,
-                    );
+                    let len = filtered_options.with_untracked(Vec::len);
+                    let current = preselected.get_untracked().map(|it| it.index);
+                    set_preselected.set(next_index(current, len).map(Preselected::keyboard));
                 }
                 "Enter" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    if let Some(preselected) = preselected.get_untracked() {
+                    let preselected = preselected.get_untracked().and_then(|preselected| {
+                        filtered_options
+                            .with_untracked(|options| options.get(preselected.index).cloned())
+                    });
+                    if let Some(preselected) = preselected {
                         if !is_disabled_untracked(&preselected) {
                             select.get_value().call(preselected)
                         }
@@ -262,31 +725,20 @@ where
                         when=move || show_options.get()
                         fallback=move || ()
                     >
-                        // TOD: Use <For> once leptos 0.4 is out. Use full option for hash.
-                        { filtered_options.get().into_iter().map(|option| {
-                            let clone1 = option.clone();
-                            let clone2 = option.clone();
-                            let clone3 = option.clone();
-                            let clone4 = option.clone();
-                            let clone5 = option.clone();
-                            view! {
-                                <leptonic-select-option
-                                    class:preselected=move || memoized_preselected.with(|preselected| preselected.as_ref() == Some(&option))
-                                    class:selected=move || is_selected(&clone4)
-                                    class:disabled=move || is_disabled(&clone5)
-                                    on:mouseenter=move |_e| {
-                                        set_preselected.set(Some(clone3.clone()));
-                                    }
-                                    on:click=move |_e| {
-                                        if !is_disabled_untracked(&clone2) {
-                                            select.get_value().call(clone2.clone())
-                                        }
-                                    }
-                                >
-                                    { render_option.get_value().call(clone1) }
-                                </leptonic-select-option>
-                            }
-                        }).collect_view() }
+                        { render_load_state(load_state) }
+
+                        <SelectOptionList
+                            options=filtered_options
+                            matches=matches
+                            group_provider=group_provider
+                            preselected=preselected
+                            set_preselected=set_preselected
+                            render_option=render_option
+                            render_match=render_match
+                            is_selected=Callback::new(move |option: O| is_selected(&option))
+                            is_disabled=Callback::new(move |option: O| is_disabled(&option))
+                            select=select
+                        />
 
                         { move || match has_options.get() {
                             true => ().into_view(),
@@ -305,11 +757,26 @@ where
 
 #[component]
 pub fn OptionalSelect<O>(
-    #[prop(into)] options: MaybeSignal<Vec<O>>,
+    /// All options, or the options shown until `loader` first responded.
+    #[prop(into, optional)]
+    options: MaybeSignal<Vec<O>>,
+    /// Loads the options matching the search text instead of filtering `options`.
+    #[prop(into, optional)]
+    loader: Option<OptionLoader<O>>,
     #[prop(into)] selected: Signal<Option<O>>,
     #[prop(into)] set_selected: Out<Option<O>>,
     #[prop(into)] search_text_provider: Callback<O, String>,
+    /// Matches the search text against the search text of each option. Defaults to a substring search.
+    #[prop(optional)]
+    matcher: Matcher,
+    /// Shows options under the header of the group returned for them. Options of the same group are shown together.
+    #[prop(into, optional)]
+    group_provider: Option<Callback<O, String>>,
     #[prop(into)] render_option: ViewCallback<O>,
+    /// Renders an offered option along with how its search text matched the search, e.g. to `<Highlight>` the matched characters.
+    /// Used instead of `render_option` in the list of offered options.
+    #[prop(into, optional)]
+    render_match: Option<ViewCallback<(O, Match)>>,
     #[prop(into)] allow_deselect: MaybeSignal<bool>,
     #[prop(into, optional)] autofocus_search: Option<Signal<bool>>,
     #[prop(into, optional)] class: Option<AttributeValue>,
@@ -334,28 +801,30 @@ where
         Signal::derive(move || show_options.get() && autofocus_search.get());
     let (search_is_focused, set_search_is_focused) = create_signal(false);
 
-    let stored_options = store_value(options);
-    let (preselected, set_preselected) = create_signal(Option::<O>::None);
-    let memoized_preselected = create_memo(move |_| preselected.get());
+    let (preselected, set_preselected) = create_signal(Option::<Preselected>::None);
 
     let (search, set_search) = create_signal("".to_owned());
 
-    let filtered_options = create_memo(move |_| {
-        let lowercased_search = search.get().to_lowercase();
-        stored_options
-            .get_value()
-            .get()
-            .into_iter()
-            .filter(|it| {
-                search_text_provider
-                    .call(it.clone())
-                    .to_lowercase()
-                    .contains(lowercased_search.as_str())
-            })
-            .collect::<Vec<_>>()
+    let (filtered_options, matches, load_state) = create_searched_options(
+        options,
+        loader,
+        search,
+        search_text_provider,
+        matcher,
+        group_provider,
+        show_options,
+    );
+
+    let has_options = create_memo(move |_| {
+        !filtered_options.with(|options| options.is_empty())
+            || load_state.with(|state| *state != LoadState::Idle)
     });
 
-    let has_options = create_memo(move |_| !filtered_options.with(|options| options.is_empty()));
+    // Indices into previously offered options are meaningless.
+    create_effect(move |_| {
+        filtered_options.track();
+        set_preselected.set(None);
+    });
 
     let set_selected_clone = set_selected.clone();
 
@@ -395,27 +864,25 @@ where
                 "ArrowUp" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    // TODO: Use options_available_for_preselect.with_untracked when https://github.com/leptos-rs/leptos/issues/1212 is resolved and released.
-                    select_previous(
-                        &filtered_options.get_untracked(),
-                        preselected,
-                        set_preselected,
-                    );
+                    let len = filtered_options.with_untracked(Vec::len);
+                    let current = preselected.get_untracked().map(|it| it.index);
+                    set_preselected.set(previous_index(current, len).map(Preselected::keyboard));
                 }
                 "ArrowDown" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    // TODO: Use options_available_for_preselect.with_untracked when https://github.com/leptos-rs/leptos/issues/1212 is resolved and released.
-                    select_next(
-                        &filtered_options.get_untracked(),
-                        preselected,
-                        set_preselected,
-                    );
+                    let len = filtered_options.with_untracked(Vec::len);
+                    let current = preselected.get_untracked().map(|it| it.index);
+                    set_preselected.set(next_index(current, len).map(Preselected::keyboard));
                 }
                 "Enter" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    if let Some(preselected) = preselected.get_untracked() {
+                    let preselected = preselected.get_untracked().and_then(|preselected| {
+                        filtered_options
+                            .with_untracked(|options| options.get(preselected.index).cloned())
+                    });
+                    if let Some(preselected) = preselected {
                         if !is_disabled_untracked(&preselected) {
                             select.get_value().call(preselected)
                         }
@@ -521,31 +988,20 @@ where
                         when=move || show_options.get()
                         fallback=move || ()
                     >
-                        // TOD: Use <For> once leptos 0.4 is out. Use full option for hash.
-                        { filtered_options.get().into_iter().map(|option| {
-                            let clone1 = option.clone();
-                            let clone2 = option.clone();
-                            let clone3 = option.clone();
-                            let clone4 = option.clone();
-                            let clone5 = option.clone();
-                            view! {
-                                <leptonic-select-option
-                                    class:preselected=move || memoized_preselected.with(|preselected| preselected.as_ref() == Some(&option))
-                                    class:selected=move || is_selected(&clone4)
-                                    class:disabled=move || is_disabled(&clone5)
-                                    on:mouseenter=move |_e| {
-                                        set_preselected.set(Some(clone3.clone()));
-                                    }
-                                    on:click=move |_e| {
-                                        if !is_disabled_untracked(&clone2) {
-                                            select.get_value().call(clone2.clone())
-                                        }
-                                    }
-                                >
-                                    { render_option.get_value().call(clone1) }
-                                </leptonic-select-option>
-                            }
-                        }).collect_view() }
+                        { render_load_state(load_state) }
+
+                        <SelectOptionList
+                            options=filtered_options
+                            matches=matches
+                            group_provider=group_provider
+                            preselected=preselected
+                            set_preselected=set_preselected
+                            render_option=render_option
+                            render_match=render_match
+                            is_selected=Callback::new(move |option: O| is_selected(&option))
+                            is_disabled=Callback::new(move |option: O| is_disabled(&option))
+                            select=select
+                        />
 
                         { move || match has_options.get() {
                             true => ().into_view(),
@@ -565,11 +1021,30 @@ where
 #[component]
 pub fn Multiselect<O>(
     #[prop(optional, default=u64::MAX)] max: u64,
-    #[prop(into)] options: MaybeSignal<Vec<O>>,
+    /// All options, or the options shown until `loader` first responded.
+    #[prop(into, optional)]
+    options: MaybeSignal<Vec<O>>,
+    /// Loads the options matching the search text instead of filtering `options`.
+    #[prop(into, optional)]
+    loader: Option<OptionLoader<O>>,
     #[prop(into)] selected: Signal<Vec<O>>,
     #[prop(into)] set_selected: Out<Vec<O>>,
     #[prop(into)] search_text_provider: Callback<O, String>,
+    /// Matches the search text against the search text of each option. Defaults to a substring search.
+    #[prop(optional)]
+    matcher: Matcher,
+    /// Shows options under the header of the group returned for them. Options of the same group are shown together.
+    #[prop(into, optional)]
+    group_provider: Option<Callback<O, String>>,
     #[prop(into)] render_option: ViewCallback<O>,
+    /// Renders an offered option along with how its search text matched the search, e.g. to `<Highlight>` the matched characters.
+    /// Used instead of `render_option` in the list of offered options.
+    #[prop(into, optional)]
+    render_match: Option<ViewCallback<(O, Match)>>,
+    /// Enables adding options which are not offered. Constructs the option for a search text matching no option.
+    /// Pasting comma-separated text adds an option for every part of it, turning the select into a tags input.
+    #[prop(into, optional)]
+    create_option: Option<Callback<String, O>>,
     #[prop(into, optional)] autofocus_search: Option<Signal<bool>>,
     #[prop(into, optional)] class: Option<AttributeValue>,
     #[prop(into, optional)] style: Option<AttributeValue>,
@@ -593,28 +1068,32 @@ where
         Signal::derive(move || show_options.get() && autofocus_search.get());
     let (search_is_focused, set_search_is_focused) = create_signal(false);
 
-    let stored_options = store_value(options);
-    let (preselected, set_preselected) = create_signal(Option::<O>::None);
-    let memoized_preselected = create_memo(move |_| preselected.get());
+    let (preselected, set_preselected) = create_signal(Option::<Preselected>::None);
 
     let (search, set_search) = create_signal("".to_owned());
 
-    let filtered_options = create_memo(move |_| {
-        let lowercased_search = search.get().to_lowercase();
-        stored_options
-            .get_value()
-            .get()
-            .into_iter()
-            .filter(|it| {
-                search_text_provider
-                    .call(it.clone())
-                    .to_lowercase()
-                    .contains(lowercased_search.as_str())
-            })
-            .collect::<Vec<_>>()
+    let known_options = options.clone();
+
+    let (filtered_options, matches, load_state) = create_searched_options(
+        options,
+        loader,
+        search,
+        search_text_provider,
+        matcher,
+        group_provider,
+        show_options,
+    );
+
+    let has_options = create_memo(move |_| {
+        !filtered_options.with(|options| options.is_empty())
+            || load_state.with(|state| *state != LoadState::Idle)
     });
 
-    let has_options = create_memo(move |_| !filtered_options.with(|options| options.is_empty()));
+    // Indices into previously offered options are meaningless.
+    create_effect(move |_| {
+        filtered_options.track();
+        set_preselected.set(None);
+    });
 
     let set_selected_clone = set_selected.clone();
 
@@ -639,6 +1118,54 @@ where
         // set_show_options.set(false); // TODO: Make this optional.
     }));
 
+    // Adds an option for every term, reusing offered options whose search text equals the term.
+    let create = StoredValue::new(Callback::new(move |input: String| {
+        let Some(create_option) = create_option else {
+            return;
+        };
+        let matching = |term: &str, options: &[O]| {
+            options
+                .iter()
+                .find(|option| {
+                    search_text_provider
+                        .call((*option).clone())
+                        .eq_ignore_ascii_case(term)
+                })
+                .cloned()
+        };
+        let mut vec = selected.get_untracked();
+        for term in split_terms(&input) {
+            if vec.len() >= max as usize {
+                break;
+            }
+            let option = filtered_options
+                .with_untracked(|options| matching(&term, options))
+                .or_else(|| known_options.with_untracked(|options| matching(&term, options)))
+                .unwrap_or_else(|| create_option.call(term));
+            if !vec.contains(&option) {
+                vec.push(option);
+            }
+        }
+        vec.sort();
+        set_selected.set(vec);
+        set_search.set(String::new());
+    }));
+
+    let creatable_term = create_memo(move |_| {
+        let term = search.with(|search| search.trim().to_owned());
+        let exists = filtered_options.with(|options| {
+            options.iter().any(|option| {
+                search_text_provider
+                    .call(option.clone())
+                    .eq_ignore_ascii_case(&term)
+            })
+        });
+        match create_option.is_some() && !term.is_empty() && !exists {
+            true => Some(term),
+            false => None,
+        }
+    });
+
     let is_selected = move |option: &O| selected.with(|selected| selected.contains(option));
 
     let is_disabled = move |option: &O| {
@@ -670,29 +1197,34 @@ where
                 "ArrowUp" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    // TODO: Use options_available_for_preselect.with_untracked when https://github.com/leptos-rs/leptos/issues/1212 is resolved and released.
-                    select_previous(
-                        &filtered_options.get_untracked(),
-                        preselected,
-                        set_preselected,
-                    );
+                    let len = filtered_options.with_untracked(Vec::len);
+                    let current = preselected.get_untracked().map(|it| it.index);
+                    set_preselected.set(previous_index(current, len).map(Preselected::keyboard));
                 }
                 "ArrowDown" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    // TODO: Use options_available_for_preselect.with_untracked when https://github.com/leptos-rs/leptos/issues/1212 is resolved and released.
-                    select_next(
-                        &filtered_options.get_untracked(),
-                        preselected,
-                        set_preselected,
-                    );
+                    let len = filtered_options.with_untracked(Vec::len);
+                    let current = preselected.get_untracked().map(|it| it.index);
+                    set_preselected.set(next_index(current, len).map(Preselected::keyboard));
                 }
                 "Enter" => {
                     e.prevent_default();
                     e.stop_propagation();
-                    if let Some(preselected) = preselected.get_untracked() {
-                        if !is_disabled_untracked(&preselected) {
-                            select.get_value().call(preselected)
+                    let preselected = preselected.get_untracked().and_then(|preselected| {
+                        filtered_options
+                            .with_untracked(|options| options.get(preselected.index).cloned())
+                    });
+                    match preselected {
+                        Some(preselected) => {
+                            if !is_disabled_untracked(&preselected) {
+                                select.get_value().call(preselected)
+                            }
+                        }
+                        None => {
+                            if let Some(term) = creatable_term.get_untracked() {
+                                create.get_value().call(term)
+                            }
                         }
                     }
                 }
@@ -773,6 +1305,22 @@ where
 
                 <leptonic-select-options class:shown=move || show_options.get()>
                     <TextInput
+                        on:paste=move |e: web_sys::Event| {
+                            use wasm_bindgen::JsCast;
+                            if create_option.is_none() {
+                                return;
+                            }
+                            let Some(pasted) = e
+                                .dyn_ref::<ClipboardEvent>()
+                                .and_then(|e| e.clipboard_data())
+                                .and_then(|data| data.get_data("text").ok()) else {
+                                return;
+                            };
+                            if pasted.contains(TERM_SEPARATORS) {
+                                e.prevent_default();
+                                create.get_value().call(format!("{}{pasted}", search.get_untracked()));
+                            }
+                        }
                         get=search
                         set=set_search
                         should_be_focused=search_should_be_focused
@@ -794,33 +1342,31 @@ where
                         when=move || show_options.get()
                         fallback=move || ()
                     >
-                        // TOD: Use <For> once leptos 0.4 is out. Use full option for hash.
-                        { filtered_options.get().into_iter().map(|option| {
-                            let clone1 = option.clone();
-                            let clone2 = option.clone();
-                            let clone3 = option.clone();
-                            let clone4 = option.clone();
-                            let clone5 = option.clone();
+                        { render_load_state(load_state) }
+
+                        { move || creatable_term.get().map(|term| {
+                            let label = format!("Add \"{term}\"");
                             view! {
-                                <leptonic-select-option
-                                    class:preselected=move || memoized_preselected.with(|preselected| preselected.as_ref() == Some(&option))
-                                    class:selected=move || is_selected(&clone4)
-                                    class:disabled=move || is_disabled(&clone5)
-                                    on:mouseenter=move |_e| {
-                                        set_preselected.set(Some(clone3.clone()));
-                                    }
-                                    on:click=move |_e| {
-                                        if !is_disabled_untracked(&clone2) {
-                                            select.get_value().call(clone2.clone())
-                                        }
-                                    }
-                                >
-                                    { render_option.get_value().call(clone1) }
-                                </leptonic-select-option>
+                                <leptonic-select-create-option on:click=move |_| create.get_value().call(term.clone())>
+                                    {label}
+                                </leptonic-select-create-option>
                             }
-                        }).collect_view() }
-
-                        { move || match has_options.get() {
+                        }) }
+
+                        <SelectOptionList
+                            options=filtered_options
+                            matches=matches
+                            group_provider=group_provider
+                            preselected=preselected
+                            set_preselected=set_preselected
+                            render_option=render_option
+                            render_match=render_match
+                            is_selected=Callback::new(move |option: O| is_selected(&option))
+                            is_disabled=Callback::new(move |option: O| is_disabled(&option))
+                            select=select
+                        />
+
+                        { move || match has_options.get() || creatable_term.with(Option::is_some) {
                             true => ().into_view(),
                             false => view! {
                                 <div class="option">
@@ -882,3 +1428,90 @@ fn create_key_down_listener<T: Fn(KeyboardEvent) + 'static>(then: T) {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        group_options, next_index, previous_index, scroll_top_revealing, split_terms,
+        visible_range, Row, Rows, OVERSCAN,
+    };
+
+    fn continent(country: &&str) -> String {
+        match *country {
+            "Chile" | "Peru" => "South America",
+            "Kenya" => "Africa",
+            _ => "Europe",
+        }
+        .to_owned()
+    }
+
+    #[test]
+    fn arrow_keys_wrap_around() {
+        assert_eq!(next_index(None, 3), Some(0));
+        assert_eq!(next_index(Some(1), 3), Some(2));
+        assert_eq!(next_index(Some(2), 3), Some(0));
+        assert_eq!(previous_index(None, 3), Some(2));
+        assert_eq!(previous_index(Some(0), 3), Some(2));
+        assert_eq!(previous_index(Some(2), 3), Some(1));
+        // Indices of options which are no longer offered start over.
+        assert_eq!(next_index(Some(7), 3), Some(0));
+        assert_eq!(previous_index(Some(7), 3), Some(2));
+        assert_eq!(next_index(None, 0), None);
+        assert_eq!(previous_index(Some(0), 0), None);
+    }
+
+    #[test]
+    fn renders_visible_options_only() {
+        assert_eq!(visible_range(0.0, 200.0, 40.0, 20_000), 0..5 + OVERSCAN);
+        assert_eq!(
+            visible_range(4000.0, 200.0, 40.0, 20_000),
+            100 - OVERSCAN..105 + OVERSCAN
+        );
+        assert_eq!(visible_range(4000.0, 200.0, 40.0, 102), 95..102);
+        assert_eq!(visible_range(0.0, 200.0, 40.0, 0), 0..0);
+    }
+
+    #[test]
+    fn scrolls_preselected_options_into_view() {
+        assert_eq!(scroll_top_revealing(2, 40.0, 0.0, 200.0), None);
+        assert_eq!(scroll_top_revealing(5, 40.0, 0.0, 200.0), Some(40.0));
+        assert_eq!(scroll_top_revealing(1, 40.0, 100.0, 200.0), Some(40.0));
+    }
+
+    #[test]
+    fn groups_keep_the_order_of_their_first_option() {
+        let countries = vec!["Chile", "France", "Kenya", "Peru", "Spain"];
+        assert_eq!(
+            group_options(countries, continent),
+            vec!["Chile", "Peru", "France", "Spain", "Kenya"]
+        );
+    }
+
+    #[test]
+    fn group_headers_precede_their_options() {
+        let groups = ["Europe", "Europe", "Africa"].map(str::to_owned);
+        let rows = Rows::grouped(groups.into_iter());
+        assert_eq!(
+            rows.rows,
+            vec![
+                Row::Group("Europe".to_owned()),
+                Row::Option(0),
+                Row::Option(1),
+                Row::Group("Africa".to_owned()),
+                Row::Option(2),
+            ]
+        );
+        assert_eq!(rows.option_rows, vec![1, 2, 4]);
+        assert_eq!(Rows::ungrouped(2).option_rows, vec![0, 1]);
+        assert!(Rows::grouped(std::iter::empty()).rows.is_empty());
+    }
+
+    #[test]
+    fn pasted_text_is_split_into_distinct_terms() {
+        assert_eq!(
+            split_terms("rust, leptos,,wasm\r\nRust\n css "),
+            vec!["rust", "leptos", "wasm", "css"]
+        );
+        assert!(split_terms(" , ").is_empty());
+    }
+}
@@ -2,9 +2,13 @@ use std::borrow::Cow;
 
 use leptos::*;
 use leptos_use::use_element_hover;
+use web_sys::KeyboardEvent;
 
 use crate::{
-    contexts::global_mouseup_event::GlobalMouseupEvent, math::project_into_range, prelude::Popover,
+    contexts::global_mouseup_event::GlobalMouseupEvent,
+    direction::{use_direction, Direction},
+    math::project_into_range,
+    prelude::Popover,
     Out, RelativeMousePosition, TrackedElementClientBoundingRect,
 };
 
@@ -141,7 +145,7 @@ fn Marks(marks: Signal<Vec<Mark>>) -> impl IntoView {
             {
                 move || marks.get().into_iter()
                     .map(|mark| {
-                        let style = format!("left: {}%", mark.percentage * 100.0);
+                        let style = format!("inset-inline-start: {}%", mark.percentage * 100.0);
                         view! {
                             <div class="mark" class:in-range=move || mark.in_range.get() style=style>
                                 { match &mark.name {
@@ -219,6 +223,7 @@ pub fn Slider(
     #[prop(into, optional)] value_display: Option<Callback<f64, String>>,
 ) -> impl IntoView {
     let range = create_memo(move |_| max - min);
+    let direction = use_direction();
 
     let bar_el: NodeRef<html::Div> = create_node_ref();
     let bar = TrackedElementClientBoundingRect::new(bar_el);
@@ -229,7 +234,7 @@ pub fn Slider(
 
     let range_style = Signal::derive(move || {
         format!(
-            "left: 0%; width: {}%;",
+            "inset-inline-start: 0%; width: {}%;",
             knob.clipped_value_percent.get() * 100.0
         )
     });
@@ -249,7 +254,7 @@ pub fn Slider(
     create_effect(move |_| {
         if knob.listening.get() {
             set_value.set(project_into_range(
-                cursor.rel_mouse_pos.get().0,
+                direction.get().mirror(cursor.rel_mouse_pos.get().0),
                 range.get(),
                 min,
                 step,
@@ -300,7 +305,19 @@ pub fn Slider(
                 <div node_ref=bar_el class="bar">
                     <div class="range" style=move || range_style.get()></div>
                     <div class="knob-wrapper">
-                        <div class="knob" node_ref=knob_el class:is-dragged=move || knob.listening.get() tabindex=0 style=move || knob.style.get()>
+                        <div
+                            class="knob"
+                            node_ref=knob_el
+                            class:is-dragged=move || knob.listening.get()
+                            tabindex=0
+                            style=move || knob.style.get()
+                            on:keydown=move |e: KeyboardEvent| {
+                                if let Some(new_value) = value_after_key(&e.key(), value.get_untracked(), min, max, step, direction.get_untracked()) {
+                                    e.prevent_default();
+                                    set_value.set(new_value);
+                                }
+                            }
+                        >
                             <Popover show=show_popover>
                                 {
                                     move || {
@@ -343,6 +360,7 @@ pub fn RangeSlider(
     #[prop(into, optional)] value_display: Option<Callback<f64, String>>,
 ) -> impl IntoView {
     let range = create_memo(move |_| max - min);
+    let direction = use_direction();
 
     let bar_el: NodeRef<html::Div> = create_node_ref();
     let bar = TrackedElementClientBoundingRect::new(bar_el);
@@ -356,7 +374,7 @@ pub fn RangeSlider(
 
     let range_style = Signal::derive(move || {
         format!(
-            "left: {}%; width: {}%;",
+            "inset-inline-start: {}%; width: {}%;",
             knob_a.clipped_value_percent.get() * 100.0,
             knob_b.clipped_value_percent.get() * 100.0 - knob_a.clipped_value_percent.get() * 100.0
         )
@@ -376,7 +394,12 @@ pub fn RangeSlider(
 
     // Project the relative cursor position into the sliders value range.
     let projected_value_from_cursor = create_memo(move |_| {
-        project_into_range(cursor.rel_mouse_pos.get().0, range.get(), min, step)
+        project_into_range(
+            direction.get().mirror(cursor.rel_mouse_pos.get().0),
+            range.get(),
+            min,
+            step,
+        )
     });
 
     // While this slider is "listening", propagate the projected value.
@@ -478,7 +501,20 @@ pub fn RangeSlider(
             <div class="bar-wrapper">
                 <div node_ref=bar_el class="bar">
                     <div class="knob-wrapper">
-                        <div class="knob" node_ref=knob_a_el class:is-dragged=move || knob_a.listening.get() tabindex=0 style=move || knob_a.style.get()>
+                        <div
+                            class="knob"
+                            node_ref=knob_a_el
+                            class:is-dragged=move || knob_a.listening.get()
+                            tabindex=0
+                            style=move || knob_a.style.get()
+                            on:keydown=move |e: KeyboardEvent| {
+                                if let Some(new_value) = value_after_key(&e.key(), value_a.get_untracked(), min, max, step, direction.get_untracked()) {
+                                    e.prevent_default();
+                                    // Knob A may not pass knob B.
+                                    set_value_a.set(new_value.min(value_b.get_untracked()));
+                                }
+                            }
+                        >
                             <Popover show=show_a_popover>
                                 {
                                     move || {
@@ -494,7 +530,20 @@ pub fn RangeSlider(
                     </div>
                     <div class="range" style=move || range_style.get()></div>
                     <div class="knob-wrapper">
-                        <div class="knob" node_ref=knob_b_el class:is-dragged=move || knob_b.listening.get() tabindex=0 style=move || knob_b.style.get()>
+                        <div
+                            class="knob"
+                            node_ref=knob_b_el
+                            class:is-dragged=move || knob_b.listening.get()
+                            tabindex=0
+                            style=move || knob_b.style.get()
+                            on:keydown=move |e: KeyboardEvent| {
+                                if let Some(new_value) = value_after_key(&e.key(), value_b.get_untracked(), min, max, step, direction.get_untracked()) {
+                                    e.prevent_default();
+                                    // Knob B may not pass knob A.
+                                    set_value_b.set(new_value.max(value_a.get_untracked()));
+                                }
+                            }
+                        >
                             <Popover show=show_b_popover>
                                 {
                                     move || {
@@ -516,6 +565,35 @@ pub fn RangeSlider(
     }
 }
 
+/// The value a focused knob should take when `key` is pressed, or `None` if the key does not move knobs.
+/// Horizontal arrow keys follow the visual direction of the slider, so that ArrowLeft increases the value in right-to-left layouts.
+/// Without a step, arrow keys move the knob by one percent of the range.
+pub(crate) fn value_after_key(
+    key: &str,
+    value: f64,
+    min: f64,
+    max: f64,
+    step: Option<f64>,
+    direction: Direction,
+) -> Option<f64> {
+    let increment = step.unwrap_or((max - min).abs() / 100.0).abs() * (max - min).signum();
+    let next = match (key, direction) {
+        ("Home", _) => return Some(min),
+        ("End", _) => return Some(max),
+        ("ArrowUp", _) | ("ArrowRight", Direction::Ltr) | ("ArrowLeft", Direction::Rtl) => {
+            value + increment
+        }
+        ("ArrowDown", _) | ("ArrowLeft", Direction::Ltr) | ("ArrowRight", Direction::Rtl) => {
+            value - increment
+        }
+        _ => return None,
+    };
+    Some(match min < max {
+        true => next.clamp(min, max),
+        false => next.clamp(max, min),
+    })
+}
+
 struct KnobControl {
     #[allow(unused)]
     clipped_value: Signal<f64>,
@@ -548,8 +626,12 @@ impl KnobControl {
         });
         let clipped_value_percent =
             Signal::derive(move || ((min.abs() - clipped_value.get()) / range.get()).abs());
-        let style =
-            Signal::derive(move || format!("left: {}%", clipped_value_percent.get() * 100.0));
+        let style = Signal::derive(move || {
+            format!(
+                "inset-inline-start: {}%",
+                clipped_value_percent.get() * 100.0
+            )
+        });
         let (listening, set_listening) = create_signal(false);
         Self {
             clipped_value,
@@ -560,3 +642,33 @@ impl KnobControl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::value_after_key;
+    use crate::direction::Direction;
+
+    #[test]
+    fn arrow_keys_follow_the_direction() {
+        let key = |key, direction| value_after_key(key, 50.0, 0.0, 100.0, Some(10.0), direction);
+        assert_eq!(key("ArrowRight", Direction::Ltr), Some(60.0));
+        assert_eq!(key("ArrowLeft", Direction::Ltr), Some(40.0));
+        assert_eq!(key("ArrowRight", Direction::Rtl), Some(40.0));
+        assert_eq!(key("ArrowLeft", Direction::Rtl), Some(60.0));
+        assert_eq!(key("ArrowUp", Direction::Rtl), Some(60.0));
+        assert_eq!(key("Home", Direction::Rtl), Some(0.0));
+        assert_eq!(key("a", Direction::Ltr), None);
+    }
+
+    #[test]
+    fn keys_respect_reversed_ranges() {
+        assert_eq!(
+            value_after_key("ArrowUp", 95.0, 100.0, 0.0, Some(10.0), Direction::Ltr),
+            Some(85.0)
+        );
+        assert_eq!(
+            value_after_key("ArrowDown", 95.0, 100.0, 0.0, Some(10.0), Direction::Ltr),
+            Some(100.0)
+        );
+    }
+}
@@ -1,14 +1,31 @@
+use std::marker::PhantomData;
+
+use indexmap::IndexMap;
 use leptos::*;
 use leptos_icons::BsIcon;
+use leptos_use::use_preferred_dark;
+use strum::IntoEnumIterator;
 
 use crate::{prelude::*, toggle::ToggleProps, toggle::ToggleSize};
 
 /// Leptonic's default themes. You may want to create your own theme-defining-type if you have additional or differently named themes.
-#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::EnumIter,
+)]
 pub enum LeptonicTheme {
     #[default]
     Light,
     Dark,
+    /// Meets the WCAG 2 AA contrast requirement for all text.
+    HighContrast,
 }
 
 impl Theme for LeptonicTheme {
@@ -16,6 +33,7 @@ impl Theme for LeptonicTheme {
         match self {
             LeptonicTheme::Light => "light",
             LeptonicTheme::Dark => "dark",
+            LeptonicTheme::HighContrast => "high-contrast",
         }
     }
 
@@ -23,6 +41,26 @@ impl Theme for LeptonicTheme {
         match self {
             LeptonicTheme::Light => BsIcon::BsSun.into(),
             LeptonicTheme::Dark => BsIcon::BsMoon.into(),
+            LeptonicTheme::HighContrast => BsIcon::BsEyeFill.into(),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            LeptonicTheme::Light => "Light",
+            LeptonicTheme::Dark => "Dark",
+            LeptonicTheme::HighContrast => "High contrast",
+        }
+    }
+
+    fn is_dark(&self) -> bool {
+        matches!(self, LeptonicTheme::Dark | LeptonicTheme::HighContrast)
+    }
+
+    fn from_color_scheme(color_scheme: ColorScheme) -> Self {
+        match color_scheme {
+            ColorScheme::Light => LeptonicTheme::Light,
+            ColorScheme::Dark => LeptonicTheme::Dark,
         }
     }
 }
@@ -32,36 +70,222 @@ pub trait Theme:
 {
     fn name(&self) -> &'static str;
     fn icon(&self) -> leptos_icons::Icon;
+
+    /// Human readable name, e.g. shown in a `<ThemeSelect>`. Defaults to `name()`.
+    fn label(&self) -> &'static str {
+        self.name()
+    }
+
+    fn is_dark(&self) -> bool {
+        false
+    }
+
+    /// Value of the `color-scheme` CSS property while this theme is active,
+    /// letting the browser style scrollbars and native form controls accordingly.
+    fn color_scheme(&self) -> ColorScheme {
+        match self.is_dark() {
+            true => ColorScheme::Dark,
+            false => ColorScheme::Light,
+        }
+    }
+
+    /// The theme used while following the operating systems color scheme.
+    /// Defaults to `Self::default()` for both schemes, so override this if your type has dark themes.
+    fn from_color_scheme(_color_scheme: ColorScheme) -> Self {
+        Self::default()
+    }
 }
 
+/// The color scheme preferred by the user, as reported through the `prefers-color-scheme` media query.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColorScheme::Light => "light",
+            ColorScheme::Dark => "dark",
+        }
+    }
+}
+
+/// Either an explicitly chosen theme or following the operating systems color scheme.
+///
+/// Serializes a chosen theme just like the theme itself, so that values persisted before `System` existed
+/// can still be read. Your theme type should therefore not serialize to `"System"`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub enum ThemeChoice<T> {
+    System,
+    #[serde(untagged)]
+    Theme(T),
+}
+
+impl<T: Theme> ThemeChoice<T> {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeChoice::System => "System",
+            ThemeChoice::Theme(theme) => theme.label(),
+        }
+    }
+
+    pub fn icon(&self) -> leptos_icons::Icon {
+        match self {
+            ThemeChoice::System => BsIcon::BsCircleHalf.into(),
+            ThemeChoice::Theme(theme) => theme.icon(),
+        }
+    }
+}
+
+/// A readable and writable theme choice, e.g. as created by `create_signal_ls`.
+pub type ThemeChoiceSignal<T> = (ReadSignal<ThemeChoice<T>>, WriteSignal<ThemeChoice<T>>);
+
 #[derive(Debug, Clone, Copy)]
 pub struct ThemeContext<T: Theme + 'static> {
-    theme: ReadSignal<T>,
-    set_theme: WriteSignal<T>,
+    theme: Signal<T>,
+    choice: Signal<ThemeChoice<T>>,
+    set_choice: Consumer<ThemeChoice<T>>,
+}
+
+impl<T: Theme + 'static> ThemeContext<T> {
+    /// The active theme. Reflects the operating systems color scheme while `ThemeChoice::System` is chosen.
+    pub fn theme(&self) -> Signal<T> {
+        self.theme
+    }
+
+    pub fn choice(&self) -> Signal<ThemeChoice<T>> {
+        self.choice
+    }
+
+    pub fn set_choice(&self, choice: ThemeChoice<T>) {
+        self.set_choice.consume(choice)
+    }
+
+    pub fn set_theme(&self, theme: T) {
+        self.set_choice(ThemeChoice::Theme(theme))
+    }
 }
 
+/// CSS custom properties, e.g. `--brand-color`, mapped to their values.
+pub type ThemeVariables = IndexMap<String, String>;
+
+/// The theme variables overridden for a subtree, including those inherited from all parent providers.
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeVariablesContext {
+    pub variables: Signal<ThemeVariables>,
+}
+
+/// The theme matching the operating systems color scheme.
+fn system_theme<T: Theme + 'static>() -> Signal<T> {
+    let prefers_dark = use_preferred_dark();
+    Signal::derive(move || {
+        T::from_color_scheme(match prefers_dark.get() {
+            true => ColorScheme::Dark,
+            false => ColorScheme::Light,
+        })
+    })
+}
+
+/// The theme active for the given choice. Reflects the operating systems color scheme while `ThemeChoice::System` is chosen.
+pub(crate) fn active_theme<T: Theme + 'static>(choice: Signal<ThemeChoice<T>>) -> Signal<T> {
+    let system_theme = system_theme::<T>();
+    Signal::derive(move || match choice.get() {
+        ThemeChoice::System => system_theme.get(),
+        ThemeChoice::Theme(theme) => theme,
+    })
+}
+
+/// Provides a theme to its subtree.
+///
+/// Use `choice` instead of `theme` to allow following the operating systems color scheme.
+///
+/// Individual theme variables can be overridden using `variables`, e.g. with a brand color loaded at runtime.
+/// Nested providers inherit the theme and the variables of their parent. Their own `variables` take precedence.
 #[component]
 pub fn ThemeProvider<T>(
     #[prop(into, optional)] theme: Option<(ReadSignal<T>, WriteSignal<T>)>,
+    #[prop(into, optional)] choice: Option<ThemeChoiceSignal<T>>,
+    #[prop(into, optional)] variables: Option<MaybeSignal<ThemeVariables>>,
     children: Children,
 ) -> impl IntoView
 where
     T: Theme + 'static,
 {
-    let (theme, set_theme) = theme.unwrap_or_else(|| create_signal(T::default()));
+    let context = match (choice, theme, use_context::<ThemeContext<T>>()) {
+        (Some((choice, set_choice)), _, _) => ThemeContext {
+            theme: active_theme(choice.into()),
+            choice: choice.into(),
+            set_choice: Consumer::new(move |choice| set_choice.set(choice)),
+        },
+        (None, None, Some(parent)) => parent,
+        (None, theme, _) => {
+            let system_theme = system_theme::<T>();
+            let (theme, set_theme) = theme.unwrap_or_else(|| create_signal(T::default()));
+            ThemeContext {
+                theme: theme.into(),
+                choice: Signal::derive(move || ThemeChoice::Theme(theme.get())),
+                // A plain theme signal can not remember `System`. Choosing it applies the current system theme once.
+                set_choice: Consumer::new(move |choice| {
+                    set_theme.set(match choice {
+                        ThemeChoice::System => system_theme.get_untracked(),
+                        ThemeChoice::Theme(theme) => theme,
+                    })
+                }),
+            }
+        }
+    };
+    let theme = context.theme;
 
-    provide_context(ThemeContext { theme, set_theme });
+    let parent_variables = use_context::<ThemeVariablesContext>().map(|parent| parent.variables);
+    let variables: Signal<ThemeVariables> = create_memo(move |_| {
+        let mut merged = parent_variables
+            .map(|parent| parent.get())
+            .unwrap_or_default();
+        if let Some(variables) = &variables {
+            variables.with(|variables| merge(&mut merged, variables));
+        }
+        merged
+    })
+    .into();
+
+    provide_context(context);
+    provide_context(ThemeVariablesContext { variables });
 
     view! {
         <leptonic-theme-provider
             data-theme=move || theme.get().name()
-            style="height: 100%; width: auto; display: contents;"
+            style=move || variables.with(style)
         >
             { children() }
         </leptonic-theme-provider>
     }
 }
 
+/// Inserts (or replaces) all `overrides`. Names missing the leading `--` are prefixed.
+fn merge(variables: &mut ThemeVariables, overrides: &ThemeVariables) {
+    for (name, value) in overrides {
+        let name = match name.starts_with("--") {
+            true => name.clone(),
+            false => format!("--{name}"),
+        };
+        variables.insert(name, value.clone());
+    }
+}
+
+/// The inline style of a provider. As `data-theme` (re-)declares every variable on the provider element,
+/// all overrides, including inherited ones, must be repeated here to take precedence.
+fn style(variables: &ThemeVariables) -> String {
+    use std::fmt::Write;
+
+    let mut style = String::from("height: 100%; width: auto; display: contents;");
+    for (name, value) in variables {
+        write!(style, " {name}: {value};").unwrap();
+    }
+    style
+}
+
 #[component]
 pub fn ThemeToggle<T>(
     off: T,
@@ -80,9 +304,9 @@ where
         state: MaybeSignal::derive(move || theme_context.theme.get() == on),
         set_state: Some(
             Callback::new(move |val: bool| {
-                theme_context.set_theme.update(|current| match val {
-                    true => *current = on,
-                    false => *current = off,
+                theme_context.set_theme(match val {
+                    true => on,
+                    false => off,
                 })
             }).into(),
         ),
@@ -105,3 +329,113 @@ where
         </leptonic-theme-toggle>
     }
 }
+
+/// Lets the user choose between a light and a dark theme or following the operating systems color scheme.
+/// Requires a `<ThemeProvider>` created with a `choice` (as done by `<Root>`) for `System` to be remembered.
+#[component]
+pub fn ThemeChoiceSwitch<T>(
+    light: T,
+    dark: T,
+    #[prop(into, optional)] class: Option<AttributeValue>,
+    #[prop(into, optional)] style: Option<AttributeValue>,
+) -> impl IntoView
+where
+    T: Theme + 'static,
+{
+    let theme_context = use_context::<ThemeContext<T>>()
+        .expect("<ThemeChoiceSwitch/> component should be nested within a <ThemeProvider/>.");
+
+    let option = move |choice: ThemeChoice<T>| {
+        view! {
+            <Button
+                on_click=move |_| theme_context.set_choice(choice)
+                variant=ButtonVariant::Outlined
+                active=Signal::derive(move || theme_context.choice.get() == choice)
+            >
+                <Icon icon=choice.icon() aria_label=choice.label()/>
+            </Button>
+        }
+    };
+
+    view! {
+        <leptonic-theme-choice-switch class=class style=style>
+            <ButtonGroup>
+                { option(ThemeChoice::Theme(light)) }
+                { option(ThemeChoice::Theme(dark)) }
+                { option(ThemeChoice::System) }
+            </ButtonGroup>
+        </leptonic-theme-choice-switch>
+    }
+}
+
+/// Lets the user choose from all variants of an enumerable theme type.
+/// Set `system` to additionally offer following the operating systems color scheme.
+#[component]
+pub fn ThemeSelect<T>(
+    #[prop(optional)] system: bool,
+    #[prop(into, optional)] class: Option<AttributeValue>,
+    #[prop(into, optional)] style: Option<AttributeValue>,
+    /// The theme type is chosen using `<ThemeSelect<MyTheme>/>`.
+    #[prop(optional)]
+    _theme: PhantomData<T>,
+) -> impl IntoView
+where
+    T: Theme + IntoEnumIterator + std::fmt::Debug + Eq + 'static,
+{
+    let theme_context = use_context::<ThemeContext<T>>()
+        .expect("<ThemeSelect/> component should be nested within a <ThemeProvider/>.");
+
+    let options = system
+        .then_some(ThemeChoice::System)
+        .into_iter()
+        .chain(T::iter().map(ThemeChoice::Theme))
+        .collect::<Vec<_>>();
+
+    view! {
+        <leptonic-theme-select class=class style=style>
+            <Select
+                options=options
+                selected=theme_context.choice
+                set_selected=move |choice| theme_context.set_choice(choice)
+                search_text_provider={ move |choice: ThemeChoice<T>| choice.label().to_owned() }
+                render_option={ move |choice: ThemeChoice<T>| view! {
+                    <Icon icon=choice.icon()/>
+                    { choice.label() }
+                } }
+            />
+        </leptonic-theme-select>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge, style, LeptonicTheme, ThemeChoice, ThemeVariables};
+
+    #[test]
+    fn theme_choice_reads_previously_persisted_themes() {
+        let choice: ThemeChoice<LeptonicTheme> = serde_json::from_str("\"Dark\"").unwrap();
+        assert_eq!(choice, ThemeChoice::Theme(LeptonicTheme::Dark));
+        let choice: ThemeChoice<LeptonicTheme> = serde_json::from_str("\"System\"").unwrap();
+        assert_eq!(choice, ThemeChoice::System);
+        assert_eq!(
+            serde_json::to_string(&ThemeChoice::Theme(LeptonicTheme::Light)).unwrap(),
+            "\"Light\""
+        );
+    }
+
+    #[test]
+    fn nested_overrides_take_precedence() {
+        let mut variables = ThemeVariables::from([
+            ("--brand-color".to_owned(), "#0b5394".to_owned()),
+            ("--grey-0".to_owned(), "#fafafa".to_owned()),
+        ]);
+        merge(
+            &mut variables,
+            &ThemeVariables::from([("brand-color".to_owned(), "#c0392b".to_owned())]),
+        );
+        assert_eq!(
+            style(&variables),
+            "height: 100%; width: auto; display: contents; --brand-color: #c0392b; --grey-0: #fafafa;"
+        );
+    }
+}